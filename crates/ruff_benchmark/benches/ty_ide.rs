@@ -0,0 +1,171 @@
+#![allow(clippy::disallowed_names)]
+use ruff_benchmark::TestFile;
+use ruff_benchmark::criterion;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use rayon::ThreadPoolBuilder;
+use rustc_hash::FxHashSet;
+
+use ruff_db::files::{File, system_path_to_file};
+use ruff_db::system::{MemoryFileSystem, SystemPath, SystemPathBuf, TestSystem};
+use ruff_python_ast::PythonVersion;
+use ruff_text_size::TextSize;
+use ty_ide::{CompletionSettings, completion, hover};
+use ty_project::metadata::options::{EnvironmentOptions, Options};
+use ty_project::metadata::value::RangedValue;
+use ty_project::{CheckMode, ProjectDatabase, ProjectMetadata};
+
+/// `ty`'s type server protocol (TSP) is a small, purpose-built set of `typeServer/*` JSON-RPC
+/// messages (see `ty_server::session::tsp`); it has no `getType`/`getMembers` methods, so there's
+/// nothing resembling "type-server throughput" to benchmark end-to-end through a running server.
+/// `ty_ide::hover` and `ty_ide::completion` are the in-process capabilities a TSP `getType`- or
+/// `getMembers`-style request would ultimately bottom out in, so these benchmarks measure those
+/// directly, the same way `ty.rs` measures `ProjectDatabase::check` rather than a server
+/// round-trip.
+struct Case {
+    db: ProjectDatabase,
+    file: File,
+}
+
+// "https://raw.githubusercontent.com/python/cpython/8e8a4baf652f6e1cee7acde9d78c4b6154539748/Lib/tomllib";
+static TOMLLIB_FILES: [TestFile; 4] = [
+    TestFile::new(
+        "tomllib/__init__.py",
+        include_str!("../resources/tomllib/__init__.py"),
+    ),
+    TestFile::new(
+        "tomllib/_parser.py",
+        include_str!("../resources/tomllib/_parser.py"),
+    ),
+    TestFile::new(
+        "tomllib/_re.py",
+        include_str!("../resources/tomllib/_re.py"),
+    ),
+    TestFile::new(
+        "tomllib/_types.py",
+        include_str!("../resources/tomllib/_types.py"),
+    ),
+];
+
+fn tomllib_path(file: &TestFile) -> SystemPathBuf {
+    SystemPathBuf::from("src").join(file.name())
+}
+
+fn setup_tomllib_case() -> Case {
+    let system = TestSystem::default();
+    let fs = system.memory_file_system().clone();
+
+    fs.write_files_all(
+        TOMLLIB_FILES
+            .iter()
+            .map(|file| (tomllib_path(file), file.code().to_string())),
+    )
+    .unwrap();
+
+    let src_root = SystemPath::new("/src");
+    let mut metadata = ProjectMetadata::discover(src_root, &system).unwrap();
+    metadata.apply_options(Options {
+        environment: Some(EnvironmentOptions {
+            python_version: Some(RangedValue::cli(PythonVersion::PY312)),
+            ..EnvironmentOptions::default()
+        }),
+        ..Options::default()
+    });
+
+    let mut db = ProjectDatabase::new(metadata, system).unwrap();
+    let mut tomllib_files = FxHashSet::default();
+    let mut parser: Option<File> = None;
+
+    for test_file in &TOMLLIB_FILES {
+        let file = system_path_to_file(&db, tomllib_path(test_file)).unwrap();
+        if test_file.name().ends_with("_parser.py") {
+            parser = Some(file);
+        }
+        tomllib_files.insert(file);
+    }
+
+    let parser = parser.unwrap();
+
+    db.set_check_mode(CheckMode::OpenFiles);
+    db.project().set_open_files(&mut db, tomllib_files);
+
+    Case { db, file: parser }
+}
+
+static RAYON_INITIALIZED: std::sync::Once = std::sync::Once::new();
+
+fn setup_rayon() {
+    // See `ty.rs`'s `setup_rayon`: we're measuring single-threaded query cost, not scheduling.
+    RAYON_INITIALIZED.call_once(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(1)
+            .use_current_thread()
+            .build_global()
+            .unwrap();
+    });
+}
+
+/// A handful of offsets into `tomllib/_parser.py` that land on an expression whose type is worth
+/// hovering over (a parameter use, an attribute access, a local variable), picked by inspection of
+/// the fixture file rather than swept exhaustively, to keep this proportional to a latency probe
+/// rather than a coverage sweep.
+fn parser_hover_offsets(source: &str) -> Vec<TextSize> {
+    ["parse_array", "out.flags", "char = src[pos]", "pos: Pos"]
+        .iter()
+        .map(|needle| {
+            let index = source
+                .find(needle)
+                .unwrap_or_else(|| panic!("fixture no longer contains {needle:?}"));
+            TextSize::try_from(index).unwrap()
+        })
+        .collect()
+}
+
+fn benchmark_hover(criterion: &mut Criterion) {
+    setup_rayon();
+
+    criterion.bench_function("ty_ide_hover[tomllib]", |b| {
+        b.iter_batched_ref(
+            || {
+                let case = setup_tomllib_case();
+                let source = ruff_db::source::source_text(&case.db, case.file);
+                let offsets = parser_hover_offsets(source.as_str());
+                (case, offsets)
+            },
+            |(case, offsets)| {
+                for offset in offsets.iter().copied() {
+                    // Not every offset resolves to a hoverable expression; we only care about the
+                    // cost of asking, matching how a TSP `getType`-style request would be used.
+                    let _ = hover(&case.db, case.file, offset);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+fn benchmark_completion(criterion: &mut Criterion) {
+    setup_rayon();
+
+    criterion.bench_function("ty_ide_completion[tomllib]", |b| {
+        b.iter_batched_ref(
+            || {
+                let case = setup_tomllib_case();
+                let source = ruff_db::source::source_text(&case.db, case.file);
+                let offsets = parser_hover_offsets(source.as_str());
+                (case, offsets)
+            },
+            |(case, offsets)| {
+                let settings = CompletionSettings { auto_import: true };
+                for offset in offsets.iter().copied() {
+                    let result = completion(&case.db, &settings, case.file, offset);
+                    std::hint::black_box(result);
+                }
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(ide, benchmark_hover, benchmark_completion);
+criterion_main!(ide);
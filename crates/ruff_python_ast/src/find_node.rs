@@ -9,6 +9,11 @@ use std::fmt::Formatter;
 /// If `range` is empty and falls within a parser *synthesized* node generated during error recovery,
 /// then the first node with the given range is returned.
 ///
+/// This only ever collects [`AnyNodeRef`]s while walking, so callers doing repeated position
+/// queries over a large parsed module (e.g. an IDE request re-checking several offsets) don't pay
+/// for cloning the candidate nodes they never end up using; only [`CoveringNode::node`] and
+/// [`CoveringNode::parent`] hand back a value, and that value is itself just a borrow.
+///
 /// ## Panics
 /// Panics if `range` is not contained within `root`.
 pub fn covering_node(root: AnyNodeRef, range: TextRange) -> CoveringNode {
@@ -134,3 +139,113 @@ impl fmt::Debug for CoveringNode<'_> {
         f.debug_tuple("CoveringNode").field(&self.node()).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::covering_node;
+    use crate::AnyNodeRef;
+    use ruff_python_parser::parse_module;
+    use ruff_text_size::{Ranged, TextRange, TextSize};
+
+    #[test]
+    fn narrowest_node_containing_range() {
+        let parsed = parse_module("x = aaa + bbb").unwrap();
+        let module = parsed.syntax();
+
+        // The range of the `aaa` name expression.
+        let range = TextRange::new(TextSize::from(4), TextSize::from(7));
+        let covering = covering_node(module.into(), range);
+
+        assert!(matches!(covering.node(), AnyNodeRef::ExprName(_)));
+        assert_eq!(covering.node().range(), range);
+
+        // Its parent should be the `aaa + bbb` binary expression, not the assignment statement.
+        assert!(matches!(covering.parent(), Some(AnyNodeRef::ExprBinOp(_))));
+    }
+
+    #[test]
+    fn covering_node_spanning_multiple_children() {
+        let parsed = parse_module("x = aaa + bbb").unwrap();
+        let module = parsed.syntax();
+
+        // A range spanning both operands selects the binary expression, not either operand.
+        let range = TextRange::new(TextSize::from(4), TextSize::from(13));
+        let covering = covering_node(module.into(), range);
+
+        assert!(matches!(covering.node(), AnyNodeRef::ExprBinOp(_)));
+    }
+
+    #[test]
+    fn empty_range_picks_narrowest_enclosing_node() {
+        let parsed = parse_module("x = aaa").unwrap();
+        let module = parsed.syntax();
+
+        // A zero-width range inside `aaa` should still resolve to the name expression.
+        let offset = TextSize::from(5);
+        let covering = covering_node(module.into(), TextRange::empty(offset));
+
+        assert!(matches!(covering.node(), AnyNodeRef::ExprName(_)));
+    }
+
+    #[test]
+    fn find_first_and_find_last_filter_by_predicate() {
+        let parsed = parse_module("x = aaa + bbb").unwrap();
+        let module = parsed.syntax();
+
+        let range = TextRange::new(TextSize::from(4), TextSize::from(7));
+        let covering = covering_node(module.into(), range);
+
+        // There's no statement expression wrapping the name, so `find_first` with a predicate
+        // that never matches returns the original `CoveringNode` unchanged, as an `Err`.
+        let not_found = covering
+            .find_first(|node| matches!(node, AnyNodeRef::StmtExpr(_)))
+            .unwrap_err();
+        assert!(matches!(not_found.node(), AnyNodeRef::ExprName(_)));
+
+        // `find_last` walks up from the name as far as the predicate still holds, stopping just
+        // below the module node (which never satisfies a non-`ModModule` predicate).
+        let covering = covering_node(module.into(), range);
+        let found = covering
+            .find_last(|node| !matches!(node, AnyNodeRef::ModModule(_)))
+            .unwrap();
+        assert!(!matches!(found.node(), AnyNodeRef::ModModule(_)));
+    }
+
+    #[test]
+    fn ancestors_returns_full_chain_from_leaf_to_root() {
+        let parsed = parse_module("x = aaa + bbb").unwrap();
+        let module = parsed.syntax();
+
+        let range = TextRange::new(TextSize::from(4), TextSize::from(7));
+        let covering = covering_node(module.into(), range);
+
+        // `ancestors` yields the full chain from the innermost covering node up to (and
+        // including) the root, not just the innermost node and its immediate parent. Callers
+        // that need to answer questions like "am I inside a call's argument list" or "which
+        // function encloses this expression" walk this chain rather than re-deriving it.
+        let kinds = covering
+            .ancestors()
+            .map(|node| node.kind())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ruff_python_ast::NodeKind::ExprName,
+                ruff_python_ast::NodeKind::ExprBinOp,
+                ruff_python_ast::NodeKind::StmtAssign,
+                ruff_python_ast::NodeKind::ModModule,
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Range is not contained within root")]
+    fn panics_if_range_is_not_contained_within_root() {
+        let parsed = parse_module("x = aaa").unwrap();
+        let module = parsed.syntax();
+
+        let out_of_bounds = TextRange::new(TextSize::from(0), TextSize::from(100));
+        covering_node(module.into(), out_of_bounds);
+    }
+}
@@ -33,9 +33,20 @@ pub(crate) enum Command {
     /// Check a project for type errors.
     Check(CheckCommand),
 
+    /// Dump the inferred type of every binding in a file or project.
+    DumpTypes(DumpTypesCommand),
+
+    /// Report the percentage of expressions and parameters with a concretely inferred type,
+    /// versus `Unknown`/`Any`.
+    TypeCoverage(TypeCoverageCommand),
+
     /// Start the language server
     Server,
 
+    /// Type-server protocol (TSP) utilities
+    #[command(subcommand)]
+    Tsp(TspCommand),
+
     /// Display ty's version
     Version,
 
@@ -44,6 +55,90 @@ pub(crate) enum Command {
     GenerateShellCompletion { shell: clap_complete_command::Shell },
 }
 
+#[derive(Debug, clap::Subcommand)]
+pub(crate) enum TspCommand {
+    /// Run a suite of protocol conformance checks against a TSP server.
+    CheckConformance(CheckConformanceCommand),
+
+    /// Query the type and documentation of a single position, without starting a language
+    /// server session.
+    GetType(GetTypeCommand),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct CheckConformanceCommand {
+    /// The command used to launch the server under test, e.g. `ty server`.
+    ///
+    /// The server is expected to speak the LSP `Content-Length`-framed JSON-RPC transport over
+    /// its stdin/stdout, the same transport this server uses - that's the only wire format a TSP
+    /// client (or server) can assume, since `typeServer/*` messages ride inside the ordinary
+    /// JSON-RPC envelope rather than defining one of their own.
+    #[arg(required = true, num_args = 1..)]
+    pub(crate) server_command: Vec<String>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct GetTypeCommand {
+    /// The position to query, as `path/to/file.py:LINE:COLUMN` (one-indexed, `COLUMN` counted
+    /// in Unicode codepoints).
+    pub(crate) location: String,
+
+    /// Run the command within the given project directory, same as `ty check --project`.
+    #[arg(long, value_name = "PROJECT")]
+    pub(crate) project: Option<SystemPathBuf>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct DumpTypesCommand {
+    /// The file or directory to dump types for. A directory is resolved the same way a project
+    /// root is: every file ty would check within it.
+    #[arg(value_name = "PATH")]
+    pub(crate) path: SystemPathBuf,
+
+    /// Run the command within the given project directory, same as `ty check --project`.
+    #[arg(long, value_name = "PROJECT")]
+    pub(crate) project: Option<SystemPathBuf>,
+
+    /// The format to emit the inferred types in.
+    #[arg(long, value_enum, default_value_t)]
+    pub(crate) format: DumpTypesFormat,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct TypeCoverageCommand {
+    /// List of files or directories to report coverage for [default: the project root].
+    #[arg(value_name = "PATH")]
+    pub(crate) paths: Vec<SystemPathBuf>,
+
+    /// Run the command within the given project directory, same as `ty check --project`.
+    #[arg(long, value_name = "PROJECT")]
+    pub(crate) project: Option<SystemPathBuf>,
+
+    /// Print a JSON report (per-file and aggregate counts) instead of a human-readable summary.
+    #[arg(long)]
+    pub(crate) output_format: Option<TypeCoverageOutputFormat>,
+}
+
+/// The output format for `ty type-coverage`.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+pub(crate) enum TypeCoverageOutputFormat {
+    /// A human-readable summary, one line per file plus a totals line (default).
+    #[default]
+    Text,
+    /// A single JSON object with per-file and aggregate counts, suitable for CI gating.
+    Json,
+}
+
+/// The output format for `ty dump-types`.
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq, PartialOrd, Ord, Default, clap::ValueEnum)]
+pub(crate) enum DumpTypesFormat {
+    /// One JSON object per binding on stdout, newline-delimited.
+    #[default]
+    Json,
+    /// A CSV table with a header row.
+    Csv,
+}
+
 #[derive(Debug, Parser)]
 #[expect(clippy::struct_excessive_bools)]
 pub(crate) struct CheckCommand {
@@ -0,0 +1,111 @@
+//! `ty dump-types`: export the inferred type of every binding in a file or project, for offline
+//! analysis pipelines and type-coverage dashboards.
+//!
+//! There's no `getTypesForFile` request to build this on - the nearest real machinery is
+//! [`ty_ide::inlay_hints`], which already walks a file's AST computing the display string for
+//! every variable binding's inferred type (it's what powers the editor's inline type hints). This
+//! reuses that same function over the whole file instead of a visible editor viewport, rather
+//! than re-deriving a second AST walk that duplicates its type-rendering logic.
+
+use std::io::Write as _;
+
+use anyhow::{Context, Result, anyhow};
+use ruff_db::files::File;
+use ruff_db::source::{line_index, source_text};
+use ruff_db::system::{OsSystem, SystemPath, SystemPathBuf};
+use ruff_text_size::{TextLen, TextRange};
+use ty_ide::{InlayHintKind, InlayHintSettings, inlay_hints};
+use ty_project::{ProjectDatabase, ProjectMetadata};
+
+use crate::args::{DumpTypesCommand, DumpTypesFormat};
+
+pub(crate) fn dump_types(args: &DumpTypesCommand) -> Result<()> {
+    let cwd = {
+        let cwd = std::env::current_dir().context("Failed to get the current working directory")?;
+        SystemPathBuf::from_path_buf(cwd)
+            .map_err(|path| anyhow!("current directory `{}` is not valid UTF-8", path.display()))?
+    };
+
+    let absolute_path = SystemPath::absolute(&args.path, &cwd);
+    let project_path = args
+        .project
+        .as_ref()
+        .map(|project| SystemPath::absolute(project, &cwd))
+        .unwrap_or_else(|| cwd.clone());
+
+    let system = OsSystem::new(&cwd);
+    let mut project_metadata = ProjectMetadata::discover(&project_path, &system)?;
+    project_metadata.apply_configuration_files(&system)?;
+
+    let mut db = ProjectDatabase::new(project_metadata, system)?;
+    db.project()
+        .set_included_paths(&mut db, vec![absolute_path.clone()]);
+
+    let files: Vec<File> = db.project().files(&db).into_iter().collect();
+
+    let settings = InlayHintSettings {
+        variable_types: true,
+        call_argument_names: false,
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    if matches!(args.format, DumpTypesFormat::Csv) {
+        writeln!(out, "file,line,column,type")?;
+    }
+
+    for file in files {
+        let path = file.path(&db);
+        let text = source_text(&db, file);
+        let index = line_index(&db, file);
+        let range = TextRange::up_to(text.as_str().text_len());
+
+        for hint in inlay_hints(&db, file, range, &settings) {
+            if !matches!(hint.kind, InlayHintKind::Type) {
+                continue;
+            }
+
+            // The label includes the leading `: `; the dump only cares about the type itself.
+            let label = hint.display().to_string();
+            let ty = label.strip_prefix(": ").unwrap_or(&label);
+            let position = index.source_location(
+                hint.position,
+                text.as_str(),
+                ruff_source_file::PositionEncoding::Utf32,
+            );
+
+            match args.format {
+                DumpTypesFormat::Json => {
+                    let record = serde_json::json!({
+                        "file": path.as_str(),
+                        "line": position.line.get(),
+                        "column": position.character_offset.get(),
+                        "type": ty,
+                    });
+                    writeln!(out, "{record}")?;
+                }
+                DumpTypesFormat::Csv => {
+                    writeln!(
+                        out,
+                        "{},{},{},{}",
+                        csv_escape(path.as_str()),
+                        position.line.get(),
+                        position.character_offset.get(),
+                        csv_escape(ty)
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,7 +1,11 @@
 mod args;
+mod dump_types;
 mod logging;
 mod printer;
 mod python_version;
+mod tsp_conformance;
+mod tsp_get_type;
+mod type_coverage;
 mod version;
 
 pub use args::Cli;
@@ -14,7 +18,7 @@ use std::process::{ExitCode, Termination};
 use anyhow::Result;
 use std::sync::Mutex;
 
-use crate::args::{CheckCommand, Command, TerminalColor};
+use crate::args::{CheckCommand, Command, TerminalColor, TspCommand};
 use crate::logging::{VerbosityLevel, setup_tracing};
 use crate::printer::Printer;
 use anyhow::{Context, anyhow};
@@ -47,6 +51,20 @@ pub fn run() -> anyhow::Result<ExitStatus> {
     match args.command {
         Command::Server => run_server().map(|()| ExitStatus::Success),
         Command::Check(check_args) => run_check(check_args),
+        Command::DumpTypes(args) => dump_types::dump_types(&args).map(|()| ExitStatus::Success),
+        Command::TypeCoverage(args) => type_coverage::type_coverage(&args),
+        Command::Tsp(TspCommand::CheckConformance(args)) => {
+            tsp_conformance::check_conformance(&args).map(|passed| {
+                if passed {
+                    ExitStatus::Success
+                } else {
+                    ExitStatus::Failure
+                }
+            })
+        }
+        Command::Tsp(TspCommand::GetType(args)) => {
+            tsp_get_type::get_type(&args).map(|()| ExitStatus::Success)
+        }
         Command::Version => version().map(|()| ExitStatus::Success),
         Command::GenerateShellCompletion { shell } => {
             use std::io::stdout;
@@ -0,0 +1,251 @@
+//! `ty tsp check-conformance`: a minimal protocol client that launches a TSP server as a
+//! subprocess and runs a handful of conformance checks against it over the real wire transport.
+//!
+//! This is intentionally scoped to what the type-server protocol (TSP) in this tree actually
+//! is: a small set of custom `typeServer/*` JSON-RPC messages riding inside an ordinary LSP
+//! session (see `ty_server::tsp`), not a standalone protocol with its own version negotiation
+//! or a notion of opaque "handles" that round-trip between client and server. Accordingly the
+//! checks here cover the LSP `initialize` handshake every server must support and the
+//! `typeServer/*` methods this server defines, rather than a generic conformance matrix for
+//! capabilities a third-party server may not have - a server that doesn't implement one of the
+//! `typeServer/*` methods is reported as unsupported, not failing.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, Command, Stdio};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Value, json};
+
+/// The outcome of a single conformance check.
+enum Outcome {
+    Pass,
+    Fail(String),
+    Unsupported,
+}
+
+struct CheckResult {
+    name: &'static str,
+    outcome: Outcome,
+}
+
+pub(crate) fn check_conformance(args: &crate::args::CheckConformanceCommand) -> Result<bool> {
+    let mut client = TspClient::spawn(&args.server_command)?;
+
+    let mut results = Vec::new();
+    results.push(run_check("initialize handshake", || client.initialize()));
+    results.push(run_check("typeServer/searchSymbols", || {
+        client.search_symbols()
+    }));
+    results.push(run_check("typeServer/visibleRanges", || {
+        client.visible_ranges()
+    }));
+
+    client.shutdown_and_exit()?;
+
+    let mut all_passed = true;
+    let server_command = args.server_command.join(" ");
+    println!("TSP conformance report for `{server_command}`:");
+    for result in &results {
+        let (marker, detail) = match &result.outcome {
+            Outcome::Pass => ("PASS", String::new()),
+            Outcome::Unsupported => {
+                ("SKIP", " (method not implemented by this server)".into())
+            }
+            Outcome::Fail(message) => {
+                all_passed = false;
+                ("FAIL", format!(": {message}"))
+            }
+        };
+        println!("  [{marker}] {}{detail}", result.name);
+    }
+
+    Ok(all_passed)
+}
+
+fn run_check(name: &'static str, check: impl FnOnce() -> Result<bool>) -> CheckResult {
+    let outcome = match check() {
+        Ok(true) => Outcome::Pass,
+        Ok(false) => Outcome::Unsupported,
+        Err(error) => Outcome::Fail(format!("{error:#}")),
+    };
+    CheckResult { name, outcome }
+}
+
+/// A bare-bones JSON-RPC client speaking the `Content-Length`-framed transport LSP (and
+/// therefore TSP) requires, wired up to a server subprocess's stdio.
+struct TspClient {
+    child: Child,
+    stdin: std::process::ChildStdin,
+    stdout: BufReader<std::process::ChildStdout>,
+    next_id: i64,
+}
+
+impl TspClient {
+    fn spawn(command: &[String]) -> Result<Self> {
+        let (program, rest) = command
+            .split_first()
+            .ok_or_else(|| anyhow!("server command must not be empty"))?;
+
+        let mut child = Command::new(program)
+            .args(rest)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("failed to launch TSP server `{program}`"))?;
+
+        let stdin = child.stdin.take().expect("piped stdin");
+        let stdout = BufReader::new(child.stdout.take().expect("piped stdout"));
+
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+            next_id: 1,
+        })
+    }
+
+    fn send_request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))?;
+
+        loop {
+            let message = self.read_message()?;
+            if message.get("id").and_then(Value::as_i64) == Some(id) {
+                return Ok(message);
+            }
+            // Notifications (e.g. `window/logMessage`) may arrive before the response; skip them.
+        }
+    }
+
+    fn send_notification(&mut self, method: &str, params: Value) -> Result<()> {
+        self.write_message(&json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "params": params,
+        }))
+    }
+
+    fn write_message(&mut self, message: &Value) -> Result<()> {
+        let body = serde_json::to_vec(message)?;
+        write!(self.stdin, "Content-Length: {}\r\n\r\n", body.len())?;
+        self.stdin.write_all(&body)?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    fn read_message(&mut self) -> Result<Value> {
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line)? == 0 {
+                bail!("server closed its stdout before responding");
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("Content-Length: ") {
+                content_length = Some(value.parse::<usize>()?);
+            }
+        }
+
+        let content_length =
+            content_length.ok_or_else(|| anyhow!("response was missing a Content-Length header"))?;
+        let mut body = vec![0u8; content_length];
+        self.stdout.read_exact(&mut body)?;
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Runs the LSP `initialize`/`initialized` handshake every server must support, returning
+    /// an error if the server doesn't respond with a well-formed result.
+    fn initialize(&mut self) -> Result<bool> {
+        let response = self.send_request(
+            "initialize",
+            json!({
+                "processId": std::process::id(),
+                "rootUri": null,
+                "capabilities": {},
+            }),
+        )?;
+
+        if response.get("error").is_some() {
+            bail!("server rejected `initialize`: {response}");
+        }
+        if response
+            .get("result")
+            .and_then(|result| result.get("capabilities"))
+            .is_none()
+        {
+            bail!("`initialize` result is missing a `capabilities` object: {response}");
+        }
+
+        self.send_notification("initialized", json!({}))?;
+        Ok(true)
+    }
+
+    /// Checks that `typeServer/searchSymbols` either returns a well-formed `SearchSymbolsResult`
+    /// or a JSON-RPC "method not found" error, the only two conformant responses for a method
+    /// this protocol doesn't mandate every server implement.
+    fn search_symbols(&mut self) -> Result<bool> {
+        let response = self.send_request("typeServer/searchSymbols", json!({"query": ""}))?;
+
+        if let Some(error) = response.get("error") {
+            if is_method_not_found(error) {
+                return Ok(false);
+            }
+            bail!("`typeServer/searchSymbols` failed with an unexpected error: {error}");
+        }
+
+        let Some(result) = response.get("result") else {
+            bail!("response had neither a `result` nor an `error`: {response}");
+        };
+
+        if !result.get("symbols").is_some_and(Value::is_array) {
+            bail!("`SearchSymbolsResult.symbols` is missing or not an array: {result}");
+        }
+        if !result.get("incomplete").is_some_and(Value::is_bool) {
+            bail!("`SearchSymbolsResult.incomplete` is missing or not a boolean: {result}");
+        }
+
+        Ok(true)
+    }
+
+    /// Checks that `typeServer/visibleRanges`, a notification, doesn't cause the server to stop
+    /// responding to subsequent requests.
+    fn visible_ranges(&mut self) -> Result<bool> {
+        self.send_notification(
+            "typeServer/visibleRanges",
+            json!({"uri": "file:///conformance-check.py", "ranges": []}),
+        )?;
+
+        // `workspace/symbol` is mandatory LSP; if the server is still alive and speaking
+        // JSON-RPC after the notification, this will get a response (result or error) either way.
+        let response = self.send_request("workspace/symbol", json!({"query": ""}))?;
+        if response.get("result").is_none() && response.get("error").is_none() {
+            bail!("server stopped responding after `typeServer/visibleRanges`: {response}");
+        }
+
+        Ok(true)
+    }
+
+    fn shutdown_and_exit(&mut self) -> Result<()> {
+        self.send_request("shutdown", Value::Null).ok();
+        self.send_notification("exit", Value::Null).ok();
+        self.child.wait()?;
+        Ok(())
+    }
+}
+
+fn is_method_not_found(error: &Value) -> bool {
+    // `MethodNotFound` per the JSON-RPC spec.
+    error.get("code").and_then(Value::as_i64) == Some(-32601)
+}
@@ -0,0 +1,96 @@
+//! `ty tsp get-type <file>:<line>:<column>`: a one-shot, in-process query for the type and
+//! documentation at a single position, for script authors and bug reporters who want an answer
+//! without wiring up an editor or a language server session.
+//!
+//! There's no standalone `getType`/`getMembers`/`getDocstring` TSP request to call into here -
+//! this calls [`ty_server::TypeServerHandle`], the same in-process API the server's
+//! `textDocument/hover` handler is built on, which is both simpler and faster than spinning up a
+//! server and a client to talk to it over a pipe for a single query.
+
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result, anyhow, bail};
+use ruff_db::system::{SystemPath, SystemPathBuf};
+use ruff_source_file::OneIndexed;
+use ty_server::TypeServerHandle;
+
+use crate::args::GetTypeCommand;
+
+pub(crate) fn get_type(args: &GetTypeCommand) -> Result<()> {
+    let (path, line, column) = parse_location(&args.location)?;
+
+    let cwd = {
+        let cwd = std::env::current_dir().context("Failed to get the current working directory")?;
+        SystemPathBuf::from_path_buf(cwd)
+            .map_err(|path| anyhow!("current directory `{}` is not valid UTF-8", path.display()))?
+    };
+
+    let project_path = args
+        .project
+        .as_ref()
+        .map(|project| SystemPath::absolute(project, &cwd))
+        .unwrap_or_else(|| cwd.clone());
+    let absolute_path = SystemPath::absolute(&path, &cwd);
+
+    let handle = TypeServerHandle::open(&project_path)?;
+    let Some(type_at_position) = handle.get_type(&absolute_path, line, column)? else {
+        println!("{}", serde_json::json!({ "contents": null }));
+        return Ok(());
+    };
+
+    println!(
+        "{}",
+        serde_json::json!({
+            "contents": type_at_position.contents,
+            "range": {
+                "start": {
+                    "line": type_at_position.start.line.get(),
+                    "column": type_at_position.start.character_offset.get(),
+                },
+                "end": {
+                    "line": type_at_position.end.line.get(),
+                    "column": type_at_position.end.character_offset.get(),
+                },
+            },
+        })
+    );
+
+    Ok(())
+}
+
+/// Parses `path:line:column` into its components. `path` itself may legitimately contain `:`
+/// (Windows drive letters), so this splits from the right.
+fn parse_location(location: &str) -> Result<(SystemPathBuf, OneIndexed, OneIndexed)> {
+    let mut parts = location.rsplitn(3, ':');
+    let column: u32 = parts
+        .next()
+        .ok_or_else(|| invalid_location(location))?
+        .parse()
+        .map_err(|_| invalid_location(location))?;
+    let line: u32 = parts
+        .next()
+        .ok_or_else(|| invalid_location(location))?
+        .parse()
+        .map_err(|_| invalid_location(location))?;
+    let path = parts.next().ok_or_else(|| invalid_location(location))?;
+
+    if column == 0 || line == 0 {
+        bail!("line and column in `{location}` must be one-indexed (>= 1)");
+    }
+
+    Ok((
+        SystemPathBuf::from(path),
+        OneIndexed::from_zero_indexed((line - 1) as usize),
+        OneIndexed::from_zero_indexed((column - 1) as usize),
+    ))
+}
+
+fn invalid_location(location: &str) -> anyhow::Error {
+    let mut message = String::new();
+    write!(
+        message,
+        "`{location}` is not a valid position; expected `path/to/file.py:LINE:COLUMN`"
+    )
+    .unwrap();
+    anyhow!(message)
+}
@@ -0,0 +1,169 @@
+//! `ty type-coverage`: report what percentage of expressions and parameters in a file or project
+//! have a concretely inferred type, as opposed to `Unknown`/`Any`, for teams adopting gradual
+//! typing who want a trackable metric.
+//!
+//! "Concretely inferred" here means anything other than [`Type::Dynamic`] - that variant covers
+//! `Any`, the internal `Unknown` (the type ty infers when it gives up, e.g. an unresolvable
+//! import), and the `Todo` placeholders used for not-yet-supported constructs, all of which are
+//! gradual types a type-coverage metric should count the same way: "not actually pinned down".
+
+use anyhow::{Context, Result, anyhow};
+use ruff_db::files::File;
+use ruff_db::parsed::parsed_module;
+use ruff_db::system::{OsSystem, SystemPath, SystemPathBuf};
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, walk_expr, walk_parameter};
+use ruff_python_ast::{Expr, Parameter};
+use ty_project::{ProjectDatabase, ProjectMetadata};
+use ty_python_semantic::types::Type;
+use ty_python_semantic::{HasType, SemanticModel};
+
+use crate::ExitStatus;
+use crate::args::{TypeCoverageCommand, TypeCoverageOutputFormat};
+
+#[derive(Default, Clone, Copy)]
+struct Coverage {
+    concrete: u32,
+    dynamic: u32,
+}
+
+impl Coverage {
+    fn total(self) -> u32 {
+        self.concrete + self.dynamic
+    }
+
+    fn percent_concrete(self) -> f64 {
+        if self.total() == 0 {
+            100.0
+        } else {
+            100.0 * f64::from(self.concrete) / f64::from(self.total())
+        }
+    }
+
+    fn record(&mut self, ty: Option<Type>) {
+        match ty {
+            Some(Type::Dynamic(_)) | None => self.dynamic += 1,
+            Some(_) => self.concrete += 1,
+        }
+    }
+
+    fn add(&mut self, other: Coverage) {
+        self.concrete += other.concrete;
+        self.dynamic += other.dynamic;
+    }
+}
+
+pub(crate) fn type_coverage(args: &TypeCoverageCommand) -> Result<ExitStatus> {
+    let cwd = {
+        let cwd = std::env::current_dir().context("Failed to get the current working directory")?;
+        SystemPathBuf::from_path_buf(cwd)
+            .map_err(|path| anyhow!("current directory `{}` is not valid UTF-8", path.display()))?
+    };
+
+    let project_path = args
+        .project
+        .as_ref()
+        .map(|project| SystemPath::absolute(project, &cwd))
+        .unwrap_or_else(|| cwd.clone());
+
+    let check_paths: Vec<_> = args
+        .paths
+        .iter()
+        .map(|path| SystemPath::absolute(path, &cwd))
+        .collect();
+
+    let system = OsSystem::new(&cwd);
+    let mut project_metadata = ProjectMetadata::discover(&project_path, &system)?;
+    project_metadata.apply_configuration_files(&system)?;
+
+    let mut db = ProjectDatabase::new(project_metadata, system)?;
+    if !check_paths.is_empty() {
+        db.project().set_included_paths(&mut db, check_paths);
+    }
+
+    let files: Vec<File> = db.project().files(&db).into_iter().collect();
+
+    let mut per_file = Vec::with_capacity(files.len());
+    let mut total = Coverage::default();
+
+    for file in files {
+        let model = SemanticModel::new(&db, file);
+        let mut visitor = CoverageVisitor {
+            model: &model,
+            coverage: Coverage::default(),
+        };
+
+        let parsed = parsed_module(&db, file).load(&db);
+        visitor.visit_body(parsed.suite());
+
+        total.add(visitor.coverage);
+        per_file.push((file.path(&db).as_str().to_string(), visitor.coverage));
+    }
+
+    per_file.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    match args.output_format.unwrap_or_default() {
+        TypeCoverageOutputFormat::Text => {
+            for (path, coverage) in &per_file {
+                println!(
+                    "{path}: {:.1}% ({}/{})",
+                    coverage.percent_concrete(),
+                    coverage.concrete,
+                    coverage.total()
+                );
+            }
+            println!(
+                "Total: {:.1}% ({}/{})",
+                total.percent_concrete(),
+                total.concrete,
+                total.total()
+            );
+        }
+        TypeCoverageOutputFormat::Json => {
+            let files: serde_json::Value = per_file
+                .iter()
+                .map(|(path, coverage)| {
+                    (
+                        path.clone(),
+                        serde_json::json!({
+                            "concrete": coverage.concrete,
+                            "dynamic": coverage.dynamic,
+                            "percentConcrete": coverage.percent_concrete(),
+                        }),
+                    )
+                })
+                .collect::<serde_json::Map<_, _>>()
+                .into();
+
+            println!(
+                "{}",
+                serde_json::json!({
+                    "files": files,
+                    "total": {
+                        "concrete": total.concrete,
+                        "dynamic": total.dynamic,
+                        "percentConcrete": total.percent_concrete(),
+                    },
+                })
+            );
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+struct CoverageVisitor<'a, 'db> {
+    model: &'a SemanticModel<'db>,
+    coverage: Coverage,
+}
+
+impl<'a> SourceOrderVisitor<'a> for CoverageVisitor<'_, '_> {
+    fn visit_expr(&mut self, expr: &'a Expr) {
+        self.coverage.record(expr.inferred_type(self.model));
+        walk_expr(self, expr);
+    }
+
+    fn visit_parameter(&mut self, parameter: &'a Parameter) {
+        self.coverage.record(parameter.inferred_type(self.model));
+        walk_parameter(self, parameter);
+    }
+}
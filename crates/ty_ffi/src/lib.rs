@@ -0,0 +1,163 @@
+//! C ABI bindings for embedding the `ty` type server in hosts that aren't written in Rust.
+//!
+//! The server normally talks JSON-RPC over stdio (see [`ty_server::run_server`]). Embedders
+//! that link against this crate instead get a handle that runs the server on a background
+//! thread and exchange raw JSON-RPC messages with it over an in-memory connection, using the
+//! same [`lsp_server::Connection::memory`] plumbing the test harness uses.
+//!
+//! All exported functions are `extern "C"` and operate on an opaque [`TyFfiServer`] pointer.
+//! Callers are responsible for the usual C ABI rules: don't call these functions from more
+//! than one thread at a time for a given handle, and don't use a handle after it's been passed
+//! to [`ty_ffi_server_shutdown`].
+
+use std::ffi::{CStr, CString, c_char};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use lsp_server::{Connection, Message};
+use ruff_db::system::OsSystem;
+
+/// An embedded `ty` type server running on a background thread.
+pub struct TyFfiServer {
+    client_connection: Connection,
+    thread: Option<jod_thread::JoinHandle<()>>,
+}
+
+/// Starts an embedded type server rooted at `workspace_path` (a NUL-terminated UTF-8 path).
+///
+/// Returns a null pointer if `workspace_path` isn't valid UTF-8 or the server fails to start.
+/// The returned handle must eventually be passed to [`ty_ffi_server_shutdown`].
+///
+/// # Safety
+///
+/// `workspace_path` must be a valid pointer to a NUL-terminated C string that remains valid
+/// for the duration of this call.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ty_ffi_server_start(workspace_path: *const c_char) -> *mut TyFfiServer {
+    let workspace_path = unsafe { CStr::from_ptr(workspace_path) };
+    let Ok(workspace_path) = workspace_path.to_str() else {
+        return std::ptr::null_mut();
+    };
+
+    let system = Arc::new(OsSystem::new(workspace_path));
+    let (server_connection, client_connection) = Connection::memory();
+
+    let worker_threads = std::thread::available_parallelism()
+        .unwrap_or_else(|_| NonZeroUsize::new(4).unwrap())
+        .min(NonZeroUsize::new(4).unwrap());
+
+    let server = match ty_server::ServerBuilder::new(server_connection, system)
+        .worker_threads(worker_threads)
+        .build()
+    {
+        Ok(server) => server,
+        Err(err) => {
+            tracing::error!("Failed to start the embedded type server: {err}");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let thread = jod_thread::Builder::new()
+        .name("ty-ffi-server".to_owned())
+        .spawn(move || {
+            if let Err(err) = server.run() {
+                tracing::error!("Embedded type server exited with an error: {err}");
+            }
+        })
+        .expect("failed to spawn the embedded type server thread");
+
+    Box::into_raw(Box::new(TyFfiServer {
+        client_connection,
+        thread: Some(thread),
+    }))
+}
+
+/// Sends a single JSON-RPC message (request, response, or notification) to the embedded server.
+///
+/// Returns `true` if the message was handed off to the server, `false` if `message` wasn't
+/// valid JSON, didn't deserialize to a JSON-RPC message, or the server has already shut down.
+///
+/// # Safety
+///
+/// `server` must be a live handle returned by [`ty_ffi_server_start`]. `message` must be a
+/// valid pointer to a NUL-terminated, UTF-8 JSON string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ty_ffi_server_send_message(
+    server: *mut TyFfiServer,
+    message: *const c_char,
+) -> bool {
+    let server = unsafe { &*server };
+    let message = unsafe { CStr::from_ptr(message) };
+
+    let Ok(message) = message.to_str() else {
+        return false;
+    };
+
+    let Ok(message) = serde_json::from_str::<Message>(message) else {
+        return false;
+    };
+
+    server.client_connection.sender.send(message).is_ok()
+}
+
+/// Polls for the next JSON-RPC message the embedded server has sent back.
+///
+/// Returns a null pointer if no message is currently available. Otherwise returns a
+/// NUL-terminated, heap-allocated JSON string that the caller must free with
+/// [`ty_ffi_string_free`].
+///
+/// # Safety
+///
+/// `server` must be a live handle returned by [`ty_ffi_server_start`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ty_ffi_server_try_recv_message(server: *mut TyFfiServer) -> *mut c_char {
+    let server = unsafe { &*server };
+
+    let Ok(message) = server.client_connection.receiver.try_recv() else {
+        return std::ptr::null_mut();
+    };
+
+    let Ok(json) = serde_json::to_string(&message) else {
+        return std::ptr::null_mut();
+    };
+
+    CString::new(json)
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// Frees a string previously returned by [`ty_ffi_server_try_recv_message`].
+///
+/// # Safety
+///
+/// `ptr` must either be null or a pointer previously returned by
+/// [`ty_ffi_server_try_recv_message`] that hasn't already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ty_ffi_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+/// Shuts down the embedded server and frees `server`.
+///
+/// Drops the client-side connection (which signals the server's main loop to exit) and blocks
+/// until the background thread joins.
+///
+/// # Safety
+///
+/// `server` must be a live handle returned by [`ty_ffi_server_start`] that hasn't already been
+/// passed to this function.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ty_ffi_server_shutdown(server: *mut TyFfiServer) {
+    if server.is_null() {
+        return;
+    }
+
+    let mut server = unsafe { Box::from_raw(server) };
+    drop(server.client_connection);
+
+    if let Some(thread) = server.thread.take() {
+        drop(thread);
+    }
+}
@@ -0,0 +1,497 @@
+//! This module implements `textDocument/prepareCallHierarchy` and the
+//! `callHierarchy/incomingCalls` and `callHierarchy/outgoingCalls` requests that follow it.
+//!
+//! Incoming calls are found by reusing the same project-wide [`references`] search that
+//! powers "find references", then filtering down to the references that are actually the
+//! callee of a call expression. Outgoing calls are found by walking the function's own body
+//! for call expressions and resolving each one with [`goto_definition`], so both directions
+//! share the same semantic resolution as the rest of the IDE layer.
+
+use crate::goto::{GotoTarget, find_goto_target};
+use crate::goto_definition::goto_definition;
+use crate::references::{ReferencesMode, references};
+use crate::{Db, NavigationTarget};
+use ruff_db::files::File;
+use ruff_db::parsed::{ParsedModuleRef, parsed_module};
+use ruff_python_ast::find_node::covering_node;
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, TraversalSignal};
+use ruff_python_ast::{self as ast, AnyNodeRef};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+use ty_python_semantic::{ImportAliasResolution, SemanticModel};
+
+/// A function or class that can appear as a node in the call hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallHierarchyItem {
+    /// The name of the function or class.
+    pub name: String,
+    /// The location of the function or class, with `focus_range` covering just its name.
+    pub target: NavigationTarget,
+}
+
+/// A caller of a [`CallHierarchyItem`], along with the ranges of the calls it makes to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingCall {
+    pub from: CallHierarchyItem,
+    pub from_ranges: Vec<TextRange>,
+}
+
+/// A callee of a [`CallHierarchyItem`], along with the ranges of the calls made to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutgoingCall {
+    pub to: CallHierarchyItem,
+    pub from_ranges: Vec<TextRange>,
+}
+
+/// Resolves the function(s) or class(es) at `offset` so that it can be used as the root of a
+/// call hierarchy.
+pub fn prepare_call_hierarchy(
+    db: &dyn Db,
+    file: File,
+    offset: TextSize,
+) -> Option<Vec<CallHierarchyItem>> {
+    let module = parsed_module(db, file).load(db);
+    let model = SemanticModel::new(db, file);
+    let goto_target = find_goto_target(&model, &module, offset)?;
+
+    let definitions = goto_target
+        .get_definition_targets(&model, ImportAliasResolution::ResolveAliases)?
+        .definition_targets(db)?;
+
+    let items: Vec<_> = definitions
+        .into_iter()
+        .filter_map(|target| call_hierarchy_item_for_target(db, target))
+        .collect();
+
+    (!items.is_empty()).then_some(items)
+}
+
+/// Finds every call to `item` across the project, grouped by the function or class that makes
+/// the call.
+pub fn incoming_calls(db: &dyn Db, item: &CallHierarchyItem) -> Vec<IncomingCall> {
+    let file = item.target.file();
+    let module = parsed_module(db, file).load(db);
+
+    let Some(callable) = callable_def_at(&module, item.target.full_range()) else {
+        return Vec::new();
+    };
+
+    let Some(reference_targets) = references(
+        db,
+        file,
+        &callable.goto_target(),
+        ReferencesMode::ReferencesSkipDeclaration,
+    ) else {
+        return Vec::new();
+    };
+
+    let mut incoming: Vec<IncomingCall> = Vec::new();
+
+    for reference in reference_targets {
+        let reference_file = reference.file();
+        let reference_module = parsed_module(db, reference_file).load(db);
+
+        if !is_call_callee(&reference_module, reference.range()) {
+            continue;
+        }
+        let Some(caller) = callable_def_at(&reference_module, reference.range()) else {
+            continue;
+        };
+
+        if let Some(existing) = incoming.iter_mut().find(|call| {
+            call.from.target.file() == reference_file
+                && call.from.target.full_range() == caller.range()
+        }) {
+            existing.from_ranges.push(reference.range());
+        } else {
+            incoming.push(IncomingCall {
+                from: CallHierarchyItem {
+                    name: caller.name().to_string(),
+                    target: NavigationTarget {
+                        file: reference_file,
+                        focus_range: caller.name_range(),
+                        full_range: caller.range(),
+                    },
+                },
+                from_ranges: vec![reference.range()],
+            });
+        }
+    }
+
+    incoming
+}
+
+/// Finds every call made from within `item`'s own body, grouped by the function or class being
+/// called.
+pub fn outgoing_calls(db: &dyn Db, item: &CallHierarchyItem) -> Vec<OutgoingCall> {
+    let file = item.target.file();
+    let module = parsed_module(db, file).load(db);
+
+    let Some(callable) = callable_def_at(&module, item.target.full_range()) else {
+        return Vec::new();
+    };
+
+    let root = callable.as_any_node_ref();
+    let mut collector = CallCollector {
+        root,
+        calls: Vec::new(),
+    };
+    root.visit_source_order(&mut collector);
+
+    let mut outgoing: Vec<OutgoingCall> = Vec::new();
+
+    for call in collector.calls {
+        let Some(result) = goto_definition(db, file, call.func.range().start()) else {
+            continue;
+        };
+
+        for target in result.value {
+            let Some(callee) = call_hierarchy_item_for_target(db, target) else {
+                continue;
+            };
+
+            if let Some(existing) = outgoing.iter_mut().find(|outgoing_call| {
+                outgoing_call.to.target.file() == callee.target.file()
+                    && outgoing_call.to.target.full_range() == callee.target.full_range()
+            }) {
+                existing.from_ranges.push(call.func.range());
+            } else {
+                outgoing.push(OutgoingCall {
+                    to: callee,
+                    from_ranges: vec![call.func.range()],
+                });
+            }
+        }
+    }
+
+    outgoing
+}
+
+/// Builds a [`CallHierarchyItem`] for `target`, provided that it actually points at a function
+/// or class definition.
+fn call_hierarchy_item_for_target(
+    db: &dyn Db,
+    target: NavigationTarget,
+) -> Option<CallHierarchyItem> {
+    let module = parsed_module(db, target.file()).load(db);
+    let callable = callable_def_at(&module, target.full_range())?;
+    Some(CallHierarchyItem {
+        name: callable.name().to_string(),
+        target,
+    })
+}
+
+/// A function or class definition, as found by walking up from some range within it.
+#[derive(Clone, Copy)]
+enum CallableDef<'a> {
+    Function(&'a ast::StmtFunctionDef),
+    Class(&'a ast::StmtClassDef),
+}
+
+impl<'a> CallableDef<'a> {
+    fn name(&self) -> &'a str {
+        match self {
+            CallableDef::Function(function) => function.name.as_str(),
+            CallableDef::Class(class) => class.name.as_str(),
+        }
+    }
+
+    fn name_range(&self) -> TextRange {
+        match self {
+            CallableDef::Function(function) => function.name.range(),
+            CallableDef::Class(class) => class.name.range(),
+        }
+    }
+
+    fn range(&self) -> TextRange {
+        match self {
+            CallableDef::Function(function) => function.range(),
+            CallableDef::Class(class) => class.range(),
+        }
+    }
+
+    fn goto_target(&self) -> GotoTarget<'a> {
+        match self {
+            CallableDef::Function(function) => GotoTarget::FunctionDef(function),
+            CallableDef::Class(class) => GotoTarget::ClassDef(class),
+        }
+    }
+
+    fn as_any_node_ref(&self) -> AnyNodeRef<'a> {
+        match self {
+            CallableDef::Function(function) => AnyNodeRef::StmtFunctionDef(function),
+            CallableDef::Class(class) => AnyNodeRef::StmtClassDef(class),
+        }
+    }
+}
+
+/// Finds the innermost function or class definition that covers `range`.
+fn callable_def_at<'a>(module: &'a ParsedModuleRef, range: TextRange) -> Option<CallableDef<'a>> {
+    let root = AnyNodeRef::from(module.syntax());
+    covering_node(root, range)
+        .ancestors()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtFunctionDef(function) => Some(CallableDef::Function(function)),
+            AnyNodeRef::StmtClassDef(class) => Some(CallableDef::Class(class)),
+            _ => None,
+        })
+}
+
+/// Returns `true` if `range` is the callee (not just an argument) of some enclosing call
+/// expression.
+fn is_call_callee(module: &ParsedModuleRef, range: TextRange) -> bool {
+    let root = AnyNodeRef::from(module.syntax());
+    covering_node(root, range).ancestors().any(|node| {
+        let AnyNodeRef::ExprCall(call) = node else {
+            return false;
+        };
+        call.func.range().contains_range(range)
+    })
+}
+
+/// Collects every call expression within a function or class body, without descending into
+/// nested function or class definitions (those calls belong to their own call hierarchy).
+struct CallCollector<'a> {
+    root: AnyNodeRef<'a>,
+    calls: Vec<&'a ast::ExprCall>,
+}
+
+impl<'a> SourceOrderVisitor<'a> for CallCollector<'a> {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        if node != self.root
+            && matches!(
+                node,
+                AnyNodeRef::StmtFunctionDef(_) | AnyNodeRef::StmtClassDef(_)
+            )
+        {
+            return TraversalSignal::Skip;
+        }
+
+        if let AnyNodeRef::ExprCall(call) = node {
+            self.calls.push(call);
+        }
+
+        TraversalSignal::Traverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{CursorTest, IntoDiagnostic, cursor_test};
+    use insta::assert_snapshot;
+    use ruff_db::diagnostic::{Annotation, Diagnostic, DiagnosticId, LintName, Severity, Span};
+
+    impl CursorTest {
+        fn prepare_call_hierarchy(&self) -> String {
+            let Some(items) =
+                prepare_call_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No call hierarchy item found".to_string();
+            };
+
+            self.render_diagnostics(items.into_iter().map(CallHierarchyItemResult))
+        }
+
+        fn incoming_calls(&self) -> String {
+            let Some(items) =
+                prepare_call_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No call hierarchy item found".to_string();
+            };
+
+            let calls: Vec<_> = items
+                .iter()
+                .flat_map(|item| incoming_calls(&self.db, item))
+                .collect();
+
+            if calls.is_empty() {
+                return "No incoming calls found".to_string();
+            }
+
+            self.render_diagnostics(calls.into_iter().map(IncomingCallResult))
+        }
+
+        fn outgoing_calls(&self) -> String {
+            let Some(items) =
+                prepare_call_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No call hierarchy item found".to_string();
+            };
+
+            let calls: Vec<_> = items
+                .iter()
+                .flat_map(|item| outgoing_calls(&self.db, item))
+                .collect();
+
+            if calls.is_empty() {
+                return "No outgoing calls found".to_string();
+            }
+
+            self.render_diagnostics(calls.into_iter().map(OutgoingCallResult))
+        }
+    }
+
+    struct CallHierarchyItemResult(CallHierarchyItem);
+
+    impl IntoDiagnostic for CallHierarchyItemResult {
+        fn into_diagnostic(self) -> Diagnostic {
+            let mut main = Diagnostic::new(
+                DiagnosticId::Lint(LintName::of("call-hierarchy")),
+                Severity::Info,
+                format!("Call hierarchy item `{}`", self.0.name),
+            );
+            main.annotate(Annotation::primary(
+                Span::from(self.0.target.file()).with_range(self.0.target.focus_range()),
+            ));
+            main
+        }
+    }
+
+    struct IncomingCallResult(IncomingCall);
+
+    impl IntoDiagnostic for IncomingCallResult {
+        fn into_diagnostic(self) -> Diagnostic {
+            let mut main = Diagnostic::new(
+                DiagnosticId::Lint(LintName::of("call-hierarchy")),
+                Severity::Info,
+                format!("Called by `{}`", self.0.from.name),
+            );
+            for range in self.0.from_ranges {
+                main.annotate(Annotation::secondary(
+                    Span::from(self.0.from.target.file()).with_range(range),
+                ));
+            }
+            main
+        }
+    }
+
+    struct OutgoingCallResult(OutgoingCall);
+
+    impl IntoDiagnostic for OutgoingCallResult {
+        fn into_diagnostic(self) -> Diagnostic {
+            let mut main = Diagnostic::new(
+                DiagnosticId::Lint(LintName::of("call-hierarchy")),
+                Severity::Info,
+                format!("Calls `{}`", self.0.to.name),
+            );
+            main.annotate(Annotation::primary(
+                Span::from(self.0.to.target.file()).with_range(self.0.to.target.focus_range()),
+            ));
+            for range in self.0.from_ranges {
+                main.annotate(Annotation::secondary(
+                    Span::from(self.0.to.target.file()).with_range(range),
+                ));
+            }
+            main
+        }
+    }
+
+    #[test]
+    fn prepare_on_function_definition() {
+        let test = cursor_test(
+            "
+def calc<CURSOR>ulate(x):
+    return x * 2
+",
+        );
+
+        assert_snapshot!(test.prepare_call_hierarchy(), @r"
+        info[call-hierarchy]: Call hierarchy item `calculate`
+         --> main.py:2:5
+          |
+        2 | def calculate(x):
+          |     ^^^^^^^^^
+        3 |     return x * 2
+          |
+        ");
+    }
+
+    #[test]
+    fn incoming_calls_across_files() {
+        let test = CursorTest::builder()
+            .source(
+                "utils.py",
+                "
+def he<CURSOR>lper(x):
+    return x * 2
+",
+            )
+            .source(
+                "main.py",
+                "
+from utils import helper
+
+def process(value):
+    return helper(value)
+
+def other():
+    return helper(1) + helper(2)
+",
+            )
+            .build();
+
+        assert_snapshot!(test.incoming_calls(), @r"
+        info[call-hierarchy]: Called by `process`
+         --> main.py:4:1
+          |
+        2 | from utils import helper
+        3 |
+        4 | def process(value):
+          | ^^^^^^^^^^^^^^^^^^^
+        5 |     return helper(value)
+          |            ------
+          |
+
+        info[call-hierarchy]: Called by `other`
+         --> main.py:7:1
+          |
+        5 |     return helper(value)
+        6 |
+        7 | def other():
+          | ^^^^^^^^^^^^
+        8 |     return helper(1) + helper(2)
+          |            ------      ------
+          |
+        ");
+    }
+
+    #[test]
+    fn outgoing_calls_skip_nested_definitions() {
+        let test = cursor_test(
+            "
+def first():
+    pass
+
+def second():
+    pass
+
+def out<CURSOR>er():
+    first()
+
+    def inner():
+        second()
+
+    return inner
+",
+        );
+
+        assert_snapshot!(test.outgoing_calls(), @r"
+        info[call-hierarchy]: Calls `first`
+         --> main.py:2:5
+          |
+        2 | def first():
+          |     ^^^^^
+        3 |     pass
+          |
+
+          ::: main.py:9:5
+           |
+         8 | def outer():
+         9 |     first()
+           |     -----
+        10 |
+        11 |     def inner():
+           |
+        ");
+    }
+}
@@ -1,13 +1,16 @@
 use crate::completion;
+use crate::goto::{GotoTarget, find_goto_target};
 
 use ruff_db::{files::File, parsed::parsed_module};
 use ruff_diagnostics::Edit;
 use ruff_python_ast::find_node::covering_node;
-use ruff_text_size::TextRange;
+use ruff_python_ast::{self as ast, AnyNodeRef};
+use ruff_text_size::{Ranged, TextRange, TextSize};
 use ty_project::Db;
 use ty_python_semantic::create_suppression_fix;
 use ty_python_semantic::lint::LintId;
 use ty_python_semantic::types::{UNDEFINED_REVEAL, UNRESOLVED_REFERENCE};
+use ty_python_semantic::SemanticModel;
 
 /// A `QuickFix` Code Action
 #[derive(Debug, Clone)]
@@ -69,6 +72,143 @@ fn unresolved_fixes(
     )
 }
 
+/// Suggests inserting an inferred type annotation at `offset`, for parameters, return types,
+/// and simple variable assignments that don't already have one.
+pub fn add_annotation_actions(db: &dyn Db, file: File, offset: TextSize) -> Vec<QuickFix> {
+    let parsed = parsed_module(db, file).load(db);
+    let model = SemanticModel::new(db, file);
+
+    let Some(goto_target) = find_goto_target(&model, &parsed, offset) else {
+        return Vec::new();
+    };
+
+    match goto_target {
+        GotoTarget::Parameter(parameter) if parameter.annotation.is_none() => {
+            annotate_parameter(db, parameter, &model)
+        }
+        GotoTarget::FunctionDef(function) if function.returns.is_none() => {
+            annotate_return_type(db, function, &model)
+        }
+        GotoTarget::Expression(expression) => {
+            annotate_variable(db, &parsed, expression, &model)
+        }
+        _ => None,
+    }
+    .into_iter()
+    .collect()
+}
+
+/// Suggests annotating an un-annotated parameter with its inferred type.
+fn annotate_parameter(
+    db: &dyn Db,
+    parameter: &ast::Parameter,
+    model: &SemanticModel,
+) -> Option<QuickFix> {
+    let ty = GotoTarget::Parameter(parameter).inferred_type(model)?;
+    let rendered = ty.display(db).to_string();
+
+    Some(QuickFix {
+        title: format!("Add parameter annotation: {rendered}"),
+        edits: vec![Edit::insertion(
+            format!(": {rendered}"),
+            parameter.name.range().end(),
+        )],
+        preferred: false,
+    })
+}
+
+/// Suggests annotating a function's return type with its inferred type.
+///
+/// The signature is rendered as a whole (rather than inferring the return expression directly)
+/// so that this goes through the same logic that powers hover, including the bail-out for
+/// functions whose type can't be narrowed down to a single overload.
+fn annotate_return_type(
+    db: &dyn Db,
+    function: &ast::StmtFunctionDef,
+    model: &SemanticModel,
+) -> Option<QuickFix> {
+    let ty = GotoTarget::FunctionDef(function).inferred_type(model)?;
+    let rendered = ty.display(db).to_string();
+    let return_ty = extract_return_type(&rendered)?;
+
+    Some(QuickFix {
+        title: format!("Add return type annotation: -> {return_ty}"),
+        edits: vec![Edit::insertion(
+            format!(" -> {return_ty}"),
+            function.parameters.range().end(),
+        )],
+        preferred: false,
+    })
+}
+
+/// Extracts the return type from a rendered function signature of the form
+/// `def name(params) -> ReturnType`, by scanning for the first top-level (bracket-depth 0)
+/// ` -> ` separator. Returns `None` for renders that don't have exactly this shape, such as
+/// overloads, which are rendered as multiple signatures on separate lines.
+fn extract_return_type(rendered: &str) -> Option<&str> {
+    if rendered.contains('\n') {
+        return None;
+    }
+
+    let mut depth = 0i32;
+    let mut indices = rendered.char_indices();
+
+    while let Some((i, c)) = indices.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ' ' if depth == 0 && rendered[i..].starts_with(" -> ") => {
+                return Some(&rendered[i + " -> ".len()..]);
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Suggests annotating a simple, un-annotated variable assignment (`x = ...`) with its
+/// inferred type.
+fn annotate_variable<'a>(
+    db: &dyn Db,
+    parsed: &ruff_db::parsed::ParsedModuleRef,
+    expression: ast::ExprRef<'a>,
+    model: &SemanticModel,
+) -> Option<QuickFix> {
+    let ast::ExprRef::Name(name) = expression else {
+        return None;
+    };
+
+    let root = AnyNodeRef::from(parsed.syntax());
+    let assign = covering_node(root, name.range())
+        .ancestors()
+        .find_map(|node| match node {
+            AnyNodeRef::StmtAssign(assign) => Some(assign),
+            _ => None,
+        })?;
+
+    // Python doesn't allow annotating one target of a multi-target or tuple-unpacking
+    // assignment, so only offer this for a single, simple name target.
+    let [target] = assign.targets.as_slice() else {
+        return None;
+    };
+    if target.range() != name.range() {
+        return None;
+    }
+
+    let ty = GotoTarget::Expression(expression).inferred_type(model)?;
+    let rendered = ty.display(db).to_string();
+
+    Some(QuickFix {
+        title: format!("Add type annotation: {rendered}"),
+        edits: vec![Edit::insertion(
+            format!(": {rendered}"),
+            name.range().end(),
+        )],
+        preferred: false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -693,6 +833,40 @@ mod tests {
         ");
     }
 
+    // unresolved reference that can be imported from a first-party module in the workspace
+    #[test]
+    fn unresolved_reference_workspace_module() {
+        let mut test = CodeActionTest::with_source(
+            r#"
+            <START>helper<END>()
+        "#,
+        );
+        test.write_file("helper_module.py", "def helper(): ...");
+
+        assert_snapshot!(test.code_actions(&UNRESOLVED_REFERENCE), @r"
+        info[code-action]: import helper_module.helper
+         --> main.py:2:1
+          |
+        2 | helper()
+          | ^^^^^^
+          |
+        help: This is a preferred code action
+        1 + from helper_module import helper
+        2 | 
+        3 | helper()
+
+        info[code-action]: Ignore 'unresolved-reference' for this line
+         --> main.py:2:1
+          |
+        2 | helper()
+          | ^^^^^^
+          |
+        1 | 
+          - helper()
+        2 + helper()  # ty:ignore[unresolved-reference]
+        ");
+    }
+
     pub(super) struct CodeActionTest {
         pub(super) db: ty_project::TestDb,
         pub(super) file: File,
@@ -738,6 +912,12 @@ mod tests {
             }
         }
 
+        pub(super) fn write_file(&mut self, path: &str, content: &str) {
+            self.db
+                .write_file(path, content)
+                .expect("write to memory file system to be successful");
+        }
+
         pub(super) fn code_actions(&self, lint: &'static LintMetadata) -> String {
             use std::fmt::Write;
 
@@ -775,4 +955,106 @@ mod tests {
             buf
         }
     }
+
+    mod annotations {
+        use crate::add_annotation_actions;
+        use crate::tests::{CursorTest, cursor_test};
+        use insta::assert_snapshot;
+
+        fn add_annotation_titles(test: &CursorTest) -> String {
+            let actions = add_annotation_actions(&test.db, test.cursor.file, test.cursor.offset);
+
+            if actions.is_empty() {
+                return "No annotation actions offered".to_string();
+            }
+
+            actions
+                .into_iter()
+                .map(|action| action.title)
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        #[test]
+        fn annotate_parameter() {
+            let test = cursor_test(
+                r#"
+                def f(a<CURSOR>):
+                    return a
+                f(1)
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"Add parameter annotation: Unknown");
+        }
+
+        #[test]
+        fn annotate_parameter_already_annotated() {
+            let test = cursor_test(
+                r#"
+                def f(a<CURSOR>: int):
+                    return a
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"No annotation actions offered");
+        }
+
+        #[test]
+        fn annotate_return_type() {
+            let test = cursor_test(
+                r#"
+                def <CURSOR>f(a: int):
+                    return a
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"Add return type annotation: -> Unknown");
+        }
+
+        #[test]
+        fn annotate_return_type_already_annotated() {
+            let test = cursor_test(
+                r#"
+                def <CURSOR>f(a: int) -> int:
+                    return a
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"No annotation actions offered");
+        }
+
+        #[test]
+        fn annotate_variable() {
+            let test = cursor_test(
+                r#"
+                val<CURSOR>ue = 1
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"Add type annotation: Literal[1]");
+        }
+
+        #[test]
+        fn annotate_variable_already_annotated() {
+            let test = cursor_test(
+                r#"
+                val<CURSOR>ue: int = 1
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"No annotation actions offered");
+        }
+
+        #[test]
+        fn annotate_variable_tuple_unpacking() {
+            let test = cursor_test(
+                r#"
+                a<CURSOR>, b = 1, 2
+                "#,
+            );
+
+            assert_snapshot!(add_annotation_titles(&test), @"No annotation actions offered");
+        }
+    }
 }
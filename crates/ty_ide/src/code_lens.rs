@@ -0,0 +1,181 @@
+//! This module implements `textDocument/codeLens` and its resolve step.
+//!
+//! [`code_lenses`] is cheap: it just walks the AST for function and class definitions and
+//! records where a lens should appear. Actually counting references or subclasses requires a
+//! project-wide search, so that's deferred to [`resolve_code_lens`], which most clients only
+//! call for the lenses currently visible on screen.
+
+use crate::find_references::find_references;
+use crate::type_hierarchy::{prepare_type_hierarchy, subtypes};
+use crate::Db;
+use ruff_db::files::File;
+use ruff_db::parsed::parsed_module;
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, TraversalSignal};
+use ruff_python_ast::AnyNodeRef;
+use ruff_text_size::{Ranged, TextRange};
+
+/// What kind of count a [`CodeLens`] shows once resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLensKind {
+    /// The number of references to a function.
+    References,
+    /// The number of direct subclasses of a class.
+    Subclasses,
+}
+
+/// A code lens anchored to a function or class definition.
+///
+/// The title isn't computed yet; call [`resolve_code_lens`] to fill it in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeLens {
+    /// The range of the `def`/`class` name the lens is attached to.
+    pub range: TextRange,
+    pub kind: CodeLensKind,
+}
+
+/// Collects one unresolved [`CodeLens`] for every function and class definition in `file`.
+pub fn code_lenses(db: &dyn Db, file: File) -> Vec<CodeLens> {
+    let module = parsed_module(db, file).load(db);
+    let root = AnyNodeRef::from(module.syntax());
+
+    let mut collector = CodeLensCollector { lenses: Vec::new() };
+    root.visit_source_order(&mut collector);
+    collector.lenses
+}
+
+/// Computes the title of `lens`, counting references (for functions) or direct subclasses
+/// (for classes).
+pub fn resolve_code_lens(db: &dyn Db, file: File, lens: &CodeLens) -> String {
+    match lens.kind {
+        CodeLensKind::References => {
+            let count = find_references(db, file, lens.range.start(), false)
+                .map(|references| references.len())
+                .unwrap_or_default();
+
+            match count {
+                0 => "no references".to_string(),
+                1 => "1 reference".to_string(),
+                _ => format!("{count} references"),
+            }
+        }
+        CodeLensKind::Subclasses => {
+            let count = prepare_type_hierarchy(db, file, lens.range.start())
+                .map(|items| {
+                    items
+                        .iter()
+                        .map(|item| subtypes(db, item).len())
+                        .sum::<usize>()
+                })
+                .unwrap_or_default();
+
+            match count {
+                0 => "no subclasses".to_string(),
+                1 => "1 subclass".to_string(),
+                _ => format!("{count} subclasses"),
+            }
+        }
+    }
+}
+
+struct CodeLensCollector {
+    lenses: Vec<CodeLens>,
+}
+
+impl SourceOrderVisitor<'_> for CodeLensCollector {
+    fn enter_node(&mut self, node: AnyNodeRef<'_>) -> TraversalSignal {
+        match node {
+            AnyNodeRef::StmtFunctionDef(function) => self.lenses.push(CodeLens {
+                range: function.name.range(),
+                kind: CodeLensKind::References,
+            }),
+            AnyNodeRef::StmtClassDef(class) => self.lenses.push(CodeLens {
+                range: class.name.range(),
+                kind: CodeLensKind::Subclasses,
+            }),
+            _ => {}
+        }
+
+        TraversalSignal::Traverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{CursorTest, cursor_test};
+
+    impl CursorTest {
+        fn code_lenses(&self) -> String {
+            let lenses = code_lenses(&self.db, self.cursor.file);
+
+            if lenses.is_empty() {
+                return "No code lenses".to_string();
+            }
+
+            lenses
+                .iter()
+                .map(|lens| resolve_code_lens(&self.db, self.cursor.file, lens))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn function_reference_count() {
+        let test = cursor_test(
+            "
+def f<CURSOR>oo():
+    pass
+
+foo()
+foo()
+",
+        );
+
+        assert_eq!(test.code_lenses(), "2 references");
+    }
+
+    #[test]
+    fn function_with_no_references() {
+        let test = cursor_test(
+            "
+def f<CURSOR>oo():
+    pass
+",
+        );
+
+        assert_eq!(test.code_lenses(), "no references");
+    }
+
+    #[test]
+    fn class_subclass_count() {
+        let test = cursor_test(
+            "
+class Ba<CURSOR>se:
+    pass
+
+class DerivedA(Base):
+    pass
+
+class DerivedB(Base):
+    pass
+",
+        );
+
+        assert_eq!(
+            test.code_lenses(),
+            "2 subclasses\nno subclasses\nno subclasses"
+        );
+    }
+
+    #[test]
+    fn no_lenses_for_module_without_defs() {
+        let test = cursor_test(
+            "
+x<CURSOR> = 1
+",
+        );
+
+        assert_eq!(test.code_lenses(), "No code lenses");
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::OnceCell;
 use std::cmp::Ordering;
 
 use ruff_db::files::File;
@@ -575,6 +576,11 @@ struct ContextCursor<'m> {
     range: TextRange,
     /// The tokens that appear before the cursor.
     tokens_before: &'m [Token],
+    /// A cache of the covering node for `range`, since several of the
+    /// heuristics below each independently need to know what AST node
+    /// covers the cursor and would otherwise each re-walk the module
+    /// from its root to find it.
+    covering_node_at_range: OnceCell<CoveringNode<'m>>,
 }
 
 impl<'m> ContextCursor<'m> {
@@ -593,6 +599,7 @@ impl<'m> ContextCursor<'m> {
                 offset,
                 range: TextRange::empty(offset),
                 tokens_before,
+                covering_node_at_range: OnceCell::new(),
             };
         };
 
@@ -608,6 +615,7 @@ impl<'m> ContextCursor<'m> {
             offset,
             range,
             tokens_before,
+            covering_node_at_range: OnceCell::new(),
         }
     }
 
@@ -645,6 +653,16 @@ impl<'m> ContextCursor<'m> {
         covering_node(self.parsed.syntax().into(), range)
     }
 
+    /// Returns the node covering `self.range`, the cursor's own range.
+    ///
+    /// Several of the heuristics below each need to know what AST node covers the cursor.
+    /// This is the same computation as `self.covering_node(self.range)`, but cached so that
+    /// asking the question more than once per completion request only walks the module once.
+    fn covering_node_at_range(&self) -> &CoveringNode<'m> {
+        self.covering_node_at_range
+            .get_or_init(|| self.covering_node(self.range))
+    }
+
     /// Whether the last token is in a place where we should not provide completions.
     fn is_in_no_completions_place(&self) -> bool {
         self.is_in_comment() || self.is_in_string() || self.is_in_definition_place()
@@ -708,7 +726,7 @@ impl<'m> ContextCursor<'m> {
     /// Returns true when the cursor sits on a binding statement.
     /// E.g. naming a parameter, type parameter, or `for` <name>).
     fn is_in_variable_binding(&self) -> bool {
-        let covering = self.covering_node(self.range);
+        let covering = self.covering_node_at_range();
         covering.ancestors().any(|node| match node {
             ast::AnyNodeRef::Parameter(param) => param.name.range.contains_range(self.range),
             ast::AnyNodeRef::TypeParamTypeVar(type_param) => {
@@ -772,7 +790,7 @@ impl<'m> ContextCursor<'m> {
         if self.is_in_decorator_expression() {
             return Some(FxHashSet::from_iter(["lambda"]));
         }
-        self.covering_node(self.range).ancestors().find_map(|node| {
+        self.covering_node_at_range().ancestors().find_map(|node| {
             self.is_in_for_statement_iterable(node)
                 .then(|| FxHashSet::from_iter(["yield", "lambda", "await"]))
                 .or_else(|| {
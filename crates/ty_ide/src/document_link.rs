@@ -0,0 +1,159 @@
+//! This module implements `textDocument/documentLink`.
+//!
+//! [`document_links`] walks the AST for `import` and `from ... import` statements and resolves
+//! each module name using the same [`SemanticModel::resolve_module`] the module resolver already
+//! exposes to goto-definition, so clicking a module name jumps straight to its source or stub
+//! file without a separate resolve round-trip.
+
+use crate::{Db, NavigationTarget};
+use ruff_db::files::File;
+use ruff_db::parsed::parsed_module;
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, TraversalSignal};
+use ruff_python_ast::{self as ast, AnyNodeRef};
+use ruff_text_size::{Ranged, TextRange};
+use ty_python_semantic::SemanticModel;
+
+/// A clickable link from a module name inside an import statement to the file it resolves to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentLink {
+    /// The range of the module name that should become clickable.
+    pub range: TextRange,
+    /// The file the module name resolves to.
+    pub target: NavigationTarget,
+}
+
+/// Collects a [`DocumentLink`] for every import statement in `file` whose module name resolves
+/// to a source or stub file.
+pub fn document_links(db: &dyn Db, file: File) -> Vec<DocumentLink> {
+    let module = parsed_module(db, file).load(db);
+    let root = AnyNodeRef::from(module.syntax());
+    let model = SemanticModel::new(db, file);
+
+    let mut collector = DocumentLinkCollector {
+        model: &model,
+        links: Vec::new(),
+    };
+    root.visit_source_order(&mut collector);
+    collector.links
+}
+
+struct DocumentLinkCollector<'a, 'db> {
+    model: &'a SemanticModel<'db>,
+    links: Vec<DocumentLink>,
+}
+
+impl DocumentLinkCollector<'_, '_> {
+    fn push_link(&mut self, range: TextRange, module: Option<&str>, level: u32) {
+        let Some(resolved) = self.model.resolve_module(module, level) else {
+            return;
+        };
+        let Some(file) = resolved.file(self.model.db()) else {
+            return;
+        };
+
+        self.links.push(DocumentLink {
+            range,
+            target: NavigationTarget::new(file, TextRange::default()),
+        });
+    }
+}
+
+impl SourceOrderVisitor<'_> for DocumentLinkCollector<'_, '_> {
+    fn enter_node(&mut self, node: AnyNodeRef<'_>) -> TraversalSignal {
+        match node {
+            AnyNodeRef::StmtImport(ast::StmtImport { names, .. }) => {
+                for alias in names {
+                    self.push_link(alias.name.range(), Some(alias.name.as_str()), 0);
+                }
+            }
+            AnyNodeRef::StmtImportFrom(import_from) => {
+                if let Some(module) = &import_from.module {
+                    self.push_link(module.range(), Some(module.as_str()), import_from.level);
+                }
+            }
+            _ => {}
+        }
+
+        TraversalSignal::Traverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{CursorTest, cursor_test};
+
+    impl CursorTest {
+        fn document_links(&self) -> String {
+            let links = document_links(&self.db, self.cursor.file);
+
+            if links.is_empty() {
+                return "No document links".to_string();
+            }
+
+            links
+                .iter()
+                .map(|link| {
+                    let text = &self.cursor.source.as_str()[link.range];
+                    format!("{text} -> {}", link.target.file().path(&self.db))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn link_for_plain_import() {
+        let test = cursor_test(
+            "
+import os<CURSOR>
+",
+        );
+
+        assert!(test.document_links().contains("os ->"));
+    }
+
+    #[test]
+    fn link_for_dotted_import() {
+        let test = cursor_test(
+            "
+import os.path<CURSOR>
+",
+        );
+
+        assert!(test.document_links().contains("os.path ->"));
+    }
+
+    #[test]
+    fn link_for_from_import() {
+        let test = cursor_test(
+            "
+from os import path<CURSOR>
+",
+        );
+
+        assert!(test.document_links().contains("os ->"));
+    }
+
+    #[test]
+    fn no_link_for_unresolved_module() {
+        let test = cursor_test(
+            "
+import definitely_not_a_real_module<CURSOR>
+",
+        );
+
+        assert_eq!(test.document_links(), "No document links");
+    }
+
+    #[test]
+    fn no_links_without_imports() {
+        let test = cursor_test(
+            "
+x<CURSOR> = 1
+",
+        );
+
+        assert_eq!(test.document_links(), "No document links");
+    }
+}
@@ -0,0 +1,283 @@
+//! Computes the edits needed to keep import statements valid when a module file is renamed or
+//! moved, in support of `workspace/willRenameFiles`.
+//!
+//! This only rewrites imports that reference the renamed module directly (`import old.mod`,
+//! `from old.mod import x` and `from old import mod`) within files that are part of the project.
+//! Relative imports and imports of submodules of the renamed module are left untouched.
+
+use crate::{Db, ModuleRenameEdit};
+use ruff_db::files::File;
+use ruff_python_ast as ast;
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, TraversalSignal, walk_stmt};
+use ruff_text_size::Ranged;
+use ty_python_semantic::{ModuleName, file_to_module};
+
+/// Returns the edits required to update every import of `old_file`'s module across the project
+/// so that it refers to `new_module_name` instead.
+///
+/// Returns `None` if `old_file` doesn't resolve to a module (e.g. it isn't part of the project).
+pub fn rename_module_imports(
+    db: &dyn Db,
+    old_file: File,
+    new_module_name: &ModuleName,
+) -> Option<Vec<ModuleRenameEdit>> {
+    let old_module_name = file_to_module(db, old_file)?.name(db).clone();
+
+    let mut edits = Vec::new();
+
+    for file in db.project().files(db) {
+        let source = ruff_db::source::source_text(db, file);
+
+        // Fast path: skip files that couldn't possibly reference the renamed module.
+        let Some(leaf) = old_module_name.components().next_back() else {
+            continue;
+        };
+        if !source.as_str().contains(leaf) {
+            continue;
+        }
+
+        edits_for_file(db, file, &old_module_name, new_module_name, &mut edits);
+    }
+
+    Some(edits)
+}
+
+fn edits_for_file(
+    db: &dyn Db,
+    file: File,
+    old_module_name: &ModuleName,
+    new_module_name: &ModuleName,
+    edits: &mut Vec<ModuleRenameEdit>,
+) {
+    let parsed = ruff_db::parsed::parsed_module(db, file).load(db);
+
+    let mut visitor = ImportRenameVisitor {
+        file,
+        old_module_name,
+        new_module_name,
+        edits,
+    };
+
+    ast::AnyNodeRef::from(parsed.syntax()).visit_source_order(&mut visitor);
+}
+
+struct ImportRenameVisitor<'a> {
+    file: File,
+    old_module_name: &'a ModuleName,
+    new_module_name: &'a ModuleName,
+    edits: &'a mut Vec<ModuleRenameEdit>,
+}
+
+impl<'ast> SourceOrderVisitor<'ast> for ImportRenameVisitor<'_> {
+    fn enter_node(&mut self, node: ast::AnyNodeRef<'ast>) -> TraversalSignal {
+        if node.is_statement() {
+            TraversalSignal::Traverse
+        } else {
+            TraversalSignal::Skip
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast ast::Stmt) {
+        match stmt {
+            ast::Stmt::Import(import) => {
+                for alias in &import.names {
+                    if alias.name.id.as_str() == self.old_module_name.as_str() {
+                        self.edits.push(ModuleRenameEdit::new(
+                            self.file,
+                            alias.name.range(),
+                            self.new_module_name.as_str().to_string(),
+                        ));
+                    }
+                }
+            }
+            ast::Stmt::ImportFrom(import_from) => {
+                // Relative imports aren't resolved here; only exact, absolute matches are safe.
+                if import_from.level == 0 {
+                    if let Some(module) = &import_from.module {
+                        if module.id.as_str() == self.old_module_name.as_str() {
+                            self.edits.push(ModuleRenameEdit::new(
+                                self.file,
+                                module.range(),
+                                self.new_module_name.as_str().to_string(),
+                            ));
+                        } else if Some(module.id.as_str())
+                            == self.old_module_name.parent().as_deref()
+                        {
+                            self.rename_submodule_alias(&import_from.names);
+                        }
+                    }
+                }
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+}
+
+impl ImportRenameVisitor<'_> {
+    /// Rewrites `from pkg import old_leaf` to `from pkg import new_leaf`.
+    ///
+    /// This rewrites `alias.name` regardless of whether the import has an `as` alias: an alias
+    /// only insulates the local *usage* of the name (`from pkg import old_leaf as x` leaves `x`
+    /// untouched everywhere it's used), but the imported name `old_leaf` in the import statement
+    /// itself still has to become `new_leaf`, or the import raises `ImportError` after the rename.
+    fn rename_submodule_alias(&mut self, names: &[ast::Alias]) {
+        let Some(old_leaf) = self.old_module_name.components().next_back() else {
+            return;
+        };
+        let Some(new_leaf) = self.new_module_name.components().next_back() else {
+            return;
+        };
+
+        for alias in names {
+            if alias.name.id.as_str() == old_leaf {
+                self.edits.push(ModuleRenameEdit::new(
+                    self.file,
+                    alias.name.range(),
+                    new_leaf.to_string(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::CursorTest;
+    use insta::assert_snapshot;
+    use ty_python_semantic::ModuleName;
+
+    impl CursorTest {
+        /// Computes the rename edits for the module containing the cursor.
+        fn rename_module_imports(&self, new_module_name: &str) -> String {
+            let new_module_name = ModuleName::new(new_module_name).unwrap();
+
+            let Some(edits) = crate::file_rename::rename_module_imports(
+                &self.db,
+                self.cursor.file,
+                &new_module_name,
+            ) else {
+                return "No module found for file".to_string();
+            };
+
+            if edits.is_empty() {
+                return "No edits".to_string();
+            }
+
+            edits
+                .iter()
+                .map(|edit| {
+                    format!(
+                        "{}:{:?} -> {:?}",
+                        edit.file().path(&self.db),
+                        edit.range(),
+                        edit.new_text()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn rename_absolute_import() {
+        let test = CursorTest::builder()
+            .source("old_mod.py", "<CURSOR>def f(): pass")
+            .source(
+                "main.py",
+                "
+import old_mod
+
+old_mod.f()
+",
+            )
+            .build();
+
+        assert_snapshot!(
+            test.rename_module_imports("new_mod"),
+            @r#"main.py:8..15 -> "new_mod""#
+        );
+    }
+
+    #[test]
+    fn rename_from_import_module() {
+        let test = CursorTest::builder()
+            .source("old_mod.py", "<CURSOR>def f(): pass")
+            .source(
+                "main.py",
+                "
+from old_mod import f
+
+f()
+",
+            )
+            .build();
+
+        assert_snapshot!(
+            test.rename_module_imports("new_mod"),
+            @r#"main.py:6..13 -> "new_mod""#
+        );
+    }
+
+    #[test]
+    fn rename_submodule_import() {
+        let test = CursorTest::builder()
+            .source("pkg/__init__.py", "")
+            .source("pkg/old_mod.py", "<CURSOR>def f(): pass")
+            .source(
+                "main.py",
+                "
+from pkg import old_mod
+
+old_mod.f()
+",
+            )
+            .build();
+
+        assert_snapshot!(
+            test.rename_module_imports("pkg.new_mod"),
+            @r#"main.py:17..24 -> "new_mod""#
+        );
+    }
+
+    #[test]
+    fn rename_submodule_import_with_alias() {
+        let test = CursorTest::builder()
+            .source("pkg/__init__.py", "")
+            .source("pkg/old_mod.py", "<CURSOR>def f(): pass")
+            .source(
+                "main.py",
+                "
+from pkg import old_mod as x
+
+x.f()
+",
+            )
+            .build();
+
+        // The `as x` alias insulates the local usage (`x.f()`), but the imported name
+        // `old_mod` in the import statement itself still has to be updated or the import
+        // breaks.
+        assert_snapshot!(
+            test.rename_module_imports("pkg.new_mod"),
+            @r#"main.py:17..24 -> "new_mod""#
+        );
+    }
+
+    #[test]
+    fn no_edits_for_unrelated_file() {
+        let test = CursorTest::builder()
+            .source("old_mod.py", "<CURSOR>def f(): pass")
+            .source(
+                "main.py",
+                "
+x = 1
+",
+            )
+            .build();
+
+        assert_snapshot!(
+            test.rename_module_imports("new_mod"),
+            @"No edits"
+        );
+    }
+}
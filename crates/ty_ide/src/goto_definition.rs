@@ -5,12 +5,17 @@ use ruff_db::parsed::parsed_module;
 use ruff_text_size::{Ranged, TextSize};
 use ty_python_semantic::{ImportAliasResolution, SemanticModel};
 
-/// Navigate to the definition of a symbol.
+/// Navigate to the definition of the symbol at `offset`.
 ///
 /// A "definition" is the actual implementation of a symbol, potentially in a source file
 /// rather than a stub file. This differs from "declaration" which may navigate to stub files.
 /// When possible, this function will map from stub file declarations to their corresponding
 /// source file implementations using the `StubMapper`.
+///
+/// This is the single convenience entry point LSP's `textDocument/definition` and similar
+/// requests call: `offset` is classified into a [`crate::goto::GotoTarget`] (a name, an
+/// attribute access, an import, a keyword argument, ...) and resolved to its definitions, without
+/// callers needing to pattern-match on the kind of symbol under the cursor themselves.
 pub fn goto_definition(
     db: &dyn Db,
     file: File,
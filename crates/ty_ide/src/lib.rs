@@ -3,11 +3,15 @@
     reason = "Prefer System trait methods over std methods in ty crates"
 )]
 mod all_symbols;
+mod call_hierarchy;
 mod code_action;
+mod code_lens;
 mod completion;
 mod doc_highlights;
 mod docstring;
+mod document_link;
 mod document_symbols;
+mod file_rename;
 mod find_references;
 mod goto;
 mod goto_declaration;
@@ -17,6 +21,7 @@ mod hover;
 mod importer;
 mod inlay_hints;
 mod markup;
+mod moniker;
 mod references;
 mod rename;
 mod selection_range;
@@ -24,13 +29,21 @@ mod semantic_tokens;
 mod signature_help;
 mod stub_mapping;
 mod symbols;
+mod type_hierarchy;
 mod workspace_symbols;
 
 pub use all_symbols::{AllSymbolInfo, all_symbols};
-pub use code_action::{QuickFix, code_actions};
+pub use call_hierarchy::{
+    CallHierarchyItem, IncomingCall, OutgoingCall, incoming_calls, outgoing_calls,
+    prepare_call_hierarchy,
+};
+pub use code_action::{QuickFix, add_annotation_actions, code_actions};
+pub use code_lens::{CodeLens, CodeLensKind, code_lenses, resolve_code_lens};
 pub use completion::{Completion, CompletionKind, CompletionSettings, completion};
 pub use doc_highlights::document_highlights;
+pub use document_link::{DocumentLink, document_links};
 pub use document_symbols::document_symbols;
+pub use file_rename::rename_module_imports;
 pub use find_references::find_references;
 pub use goto::{goto_declaration, goto_definition, goto_type_definition};
 pub use hover::hover;
@@ -38,6 +51,7 @@ pub use inlay_hints::{
     InlayHintKind, InlayHintLabel, InlayHintSettings, InlayHintTextEdit, inlay_hints,
 };
 pub use markup::MarkupKind;
+pub use moniker::{Moniker, MonikerKind, MonikerUniqueness, monikers};
 pub use references::ReferencesMode;
 pub use rename::{can_rename, rename};
 pub use selection_range::selection_range;
@@ -46,6 +60,7 @@ pub use semantic_tokens::{
 };
 pub use signature_help::{ParameterDetails, SignatureDetails, SignatureHelpInfo, signature_help};
 pub use symbols::{FlatSymbols, HierarchicalSymbols, SymbolId, SymbolInfo, SymbolKind};
+pub use type_hierarchy::{TypeHierarchyItem, prepare_type_hierarchy, subtypes, supertypes};
 pub use workspace_symbols::{WorkspaceSymbolInfo, workspace_symbols};
 
 use ruff_db::{
@@ -198,6 +213,35 @@ impl ReferenceTarget {
     }
 }
 
+/// A text edit required to keep an import statement valid after a module has been renamed or
+/// moved, e.g. in response to `workspace/willRenameFiles`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleRenameEdit {
+    file_range: FileRange,
+    new_text: String,
+}
+
+impl ModuleRenameEdit {
+    pub fn new(file: File, range: TextRange, new_text: String) -> Self {
+        Self {
+            file_range: FileRange::new(file, range),
+            new_text,
+        }
+    }
+
+    pub fn file(&self) -> File {
+        self.file_range.file()
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.file_range.range()
+    }
+
+    pub fn new_text(&self) -> &str {
+        &self.new_text
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NavigationTargets(smallvec::SmallVec<[NavigationTarget; 1]>);
 
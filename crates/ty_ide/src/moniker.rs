@@ -0,0 +1,187 @@
+//! This module implements `textDocument/moniker`.
+//!
+//! Monikers are stable, scheme-qualified identifiers for a symbol that can be compared across
+//! documents (and, for `Scheme`-unique monikers, across projects), which is what makes
+//! cross-repository code-intelligence pipelines like LSIF and SCIP able to stitch references
+//! to the same symbol together.
+
+use crate::goto::find_goto_target;
+use crate::{Db, NavigationTarget, RangedValue};
+use ruff_db::files::{File, FileRange};
+use ruff_db::parsed::parsed_module;
+use ruff_db::source::source_text;
+use ruff_text_size::{Ranged, TextSize};
+use ty_python_semantic::{ImportAliasResolution, SearchPath, SemanticModel, file_to_module};
+
+/// The scheme under which ty's monikers are minted.
+///
+/// This mirrors the `scheme` field of `lsp_types::Moniker` and distinguishes ty's monikers from
+/// those emitted by other tools that might be stitched together in the same index.
+pub const MONIKER_SCHEME: &str = "ty";
+
+/// How widely the identifier in a [`Moniker`] can be trusted to be unique.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonikerUniqueness {
+    /// The identifier is only guaranteed to be unique within the project that defines it.
+    Project,
+
+    /// The identifier is unique across every project that uses the same [`MONIKER_SCHEME`], e.g.
+    /// a symbol from the standard library or a third-party package.
+    Scheme,
+}
+
+/// The relationship between the symbol under the cursor and the moniker's target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonikerKind {
+    /// The symbol is defined in the same file that's being queried.
+    Export,
+
+    /// The symbol is defined in a different file than the one being queried.
+    Import,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Moniker {
+    pub scheme: &'static str,
+    pub identifier: String,
+    pub unique: MonikerUniqueness,
+    pub kind: MonikerKind,
+}
+
+/// Returns the monikers for the symbol at `offset` in `file`, if any.
+pub fn monikers(db: &dyn Db, file: File, offset: TextSize) -> Option<RangedValue<Vec<Moniker>>> {
+    let parsed = parsed_module(db, file).load(db);
+    let model = SemanticModel::new(db, file);
+    let goto_target = find_goto_target(&model, &parsed, offset)?;
+
+    let targets = goto_target
+        .get_definition_targets(&model, ImportAliasResolution::ResolveAliases)?
+        .definition_targets(db)?;
+
+    let monikers: Vec<Moniker> = targets
+        .into_iter()
+        .filter_map(|target| moniker_for_target(db, file, &target))
+        .collect();
+
+    if monikers.is_empty() {
+        return None;
+    }
+
+    Some(RangedValue {
+        range: FileRange::new(file, goto_target.range()),
+        value: monikers,
+    })
+}
+
+fn moniker_for_target(db: &dyn Db, origin: File, target: &NavigationTarget) -> Option<Moniker> {
+    let target_file = target.file();
+    let module = file_to_module(db, target_file)?;
+    let module_name = module.name(db);
+
+    let source = source_text(db, target_file);
+    let symbol_name = &source[target.focus_range()];
+    if symbol_name.is_empty() {
+        return None;
+    }
+
+    let identifier = format!("{module_name}.{symbol_name}");
+    let is_first_party = module
+        .search_path(db)
+        .is_some_and(SearchPath::is_first_party);
+    let unique = if is_first_party {
+        MonikerUniqueness::Project
+    } else {
+        MonikerUniqueness::Scheme
+    };
+    let kind = if target_file == origin {
+        MonikerKind::Export
+    } else {
+        MonikerKind::Import
+    };
+
+    Some(Moniker {
+        scheme: MONIKER_SCHEME,
+        identifier,
+        unique,
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moniker::{MonikerKind, MonikerUniqueness};
+    use crate::tests::CursorTest;
+    use insta::assert_snapshot;
+
+    impl CursorTest {
+        fn monikers(&self) -> String {
+            let Some(result) =
+                crate::moniker::monikers(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No monikers found".to_string();
+            };
+
+            result
+                .value
+                .iter()
+                .map(|moniker| {
+                    format!(
+                        "{}:{} ({:?}, {:?})",
+                        moniker.scheme, moniker.identifier, moniker.unique, moniker.kind
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    }
+
+    #[test]
+    fn moniker_for_local_function_definition() {
+        let test = CursorTest::builder()
+            .source(
+                "main.py",
+                "
+def my_f<CURSOR>unction():
+    pass
+",
+            )
+            .build();
+
+        assert_snapshot!(test.monikers(), @"ty:main.my_function (Project, Export)");
+    }
+
+    #[test]
+    fn moniker_for_imported_symbol() {
+        let test = CursorTest::builder()
+            .source(
+                "main.py",
+                "
+from other import my_f<CURSOR>unction
+",
+            )
+            .source(
+                "other.py",
+                "
+def my_function():
+    pass
+",
+            )
+            .build();
+
+        assert_snapshot!(test.monikers(), @"ty:other.my_function (Project, Import)");
+    }
+
+    #[test]
+    fn no_moniker_for_literal() {
+        let test = CursorTest::builder()
+            .source(
+                "main.py",
+                "
+x = 1<CURSOR>
+",
+            )
+            .build();
+
+        assert_snapshot!(test.monikers(), @"No monikers found");
+    }
+}
@@ -14,12 +14,14 @@ use crate::goto::GotoTarget;
 use crate::{Db, NavigationTargets, ReferenceKind, ReferenceTarget};
 use ruff_db::files::File;
 use ruff_python_ast::find_node::CoveringNode;
-use ruff_python_ast::token::Tokens;
+use ruff_python_ast::name::Name;
+use ruff_python_ast::token::{TokenKind, Tokens};
 use ruff_python_ast::{
     self as ast, AnyNodeRef,
     visitor::source_order::{SourceOrderVisitor, TraversalSignal},
 };
 use ruff_text_size::{Ranged, TextRange};
+use rustc_hash::FxHashSet;
 use ty_python_semantic::{ImportAliasResolution, SemanticModel};
 
 /// Mode for references search behavior
@@ -113,9 +115,11 @@ pub(crate) fn references(
                 continue;
             }
 
-            // First do a simple text search to see if there is a potential match in the file
-            let source = ruff_db::source::source_text(db, other_file);
-            if !source.as_str().contains(target_text.as_ref()) {
+            // First check the file's cached identifier set to see if there is a potential
+            // match; this is cheaper than the full semantic analysis below and, since it's
+            // salsa-tracked per file, is only recomputed when `other_file` itself changes
+            // rather than on every reference query.
+            if !identifiers_in_file(db, other_file).contains(target_text.as_ref()) {
                 continue;
             }
 
@@ -138,6 +142,33 @@ pub(crate) fn references(
     }
 }
 
+/// Returns the set of all `Name` token texts that appear anywhere in `file`.
+///
+/// This is a cheap, salsa-tracked pre-filter for cross-file reference search: a file can only
+/// contain a reference to a symbol named `target_text` if that identifier was lexed somewhere in
+/// it. Unlike a raw substring search over the file's source, this can't produce false positives
+/// from the identifier appearing inside a string or comment, and unlike a plain substring check
+/// it's a proper set membership test rather than a scan, though the real cost saved here is that
+/// salsa only recomputes it when `file` itself changes, rather than once per reference query.
+///
+/// This is deliberately not a full project-wide symbol-to-use-site index (which would need to
+/// additionally distinguish *which* symbol a given identifier occurrence binds to, and merge
+/// results across files incrementally); it only narrows down which files are worth the expense of
+/// [`references_for_file`]'s real semantic search.
+#[salsa::tracked(returns(ref), heap_size = ruff_memory_usage::heap_size)]
+fn identifiers_in_file(db: &dyn Db, file: File) -> FxHashSet<Name> {
+    let parsed = ruff_db::parsed::parsed_module(db, file);
+    let module = parsed.load(db);
+    let source = ruff_db::source::source_text(db, file);
+
+    module
+        .tokens()
+        .iter()
+        .filter(|token| token.kind() == TokenKind::Name)
+        .map(|token| Name::new(&source[token.range()]))
+        .collect()
+}
+
 /// Find all references to a local symbol within the current file.
 /// The behavior depends on the provided mode.
 fn references_for_file(
@@ -0,0 +1,347 @@
+//! This module implements `textDocument/prepareTypeHierarchy` and the
+//! `typeHierarchy/supertypes` and `typeHierarchy/subtypes` requests that follow it.
+//!
+//! Supertypes only need a class's own base-class expressions: each one is resolved with
+//! [`goto_definition`], and a client that wants to go further up the hierarchy simply calls
+//! `supertypes` again on the result. Subtypes have no such local starting point, so they're
+//! found with a project-wide search in the same spirit as [`crate::references`]: every file is
+//! text-prefiltered for the class's name before being parsed and checked for a class whose bases
+//! resolve back to the target.
+
+use crate::{Db, NavigationTarget};
+use ruff_db::files::File;
+use ruff_db::parsed::{ParsedModuleRef, parsed_module};
+use ruff_db::source::source_text;
+use ruff_python_ast::find_node::covering_node;
+use ruff_python_ast::visitor::source_order::{SourceOrderVisitor, TraversalSignal};
+use ruff_python_ast::{self as ast, AnyNodeRef};
+use ruff_text_size::{Ranged, TextRange, TextSize};
+use ty_python_semantic::{ImportAliasResolution, SemanticModel};
+
+use crate::goto::find_goto_target;
+use crate::goto_definition::goto_definition;
+
+/// A class that can appear as a node in the type hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeHierarchyItem {
+    /// The name of the class.
+    pub name: String,
+    /// The location of the class, with `focus_range` covering just its name.
+    pub target: NavigationTarget,
+}
+
+/// Resolves the class at `offset` so that it can be used as the root of a type hierarchy.
+pub fn prepare_type_hierarchy(
+    db: &dyn Db,
+    file: File,
+    offset: TextSize,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let module = parsed_module(db, file).load(db);
+    let model = SemanticModel::new(db, file);
+    let goto_target = find_goto_target(&model, &module, offset)?;
+
+    let definitions = goto_target
+        .get_definition_targets(&model, ImportAliasResolution::ResolveAliases)?
+        .definition_targets(db)?;
+
+    let items: Vec<_> = definitions
+        .into_iter()
+        .filter_map(|target| type_hierarchy_item_for_target(db, target))
+        .collect();
+
+    (!items.is_empty()).then_some(items)
+}
+
+/// Resolves the direct base classes of `item`.
+pub fn supertypes(db: &dyn Db, item: &TypeHierarchyItem) -> Vec<TypeHierarchyItem> {
+    let file = item.target.file();
+    let module = parsed_module(db, file).load(db);
+
+    let Some(class) = class_def_at(&module, item.target.full_range()) else {
+        return Vec::new();
+    };
+
+    let Some(arguments) = class.arguments.as_deref() else {
+        return Vec::new();
+    };
+
+    arguments
+        .args
+        .iter()
+        .filter_map(|base| {
+            let result = goto_definition(db, file, base.range().start())?;
+            result
+                .value
+                .into_iter()
+                .find_map(|target| type_hierarchy_item_for_target(db, target))
+        })
+        .collect()
+}
+
+/// Finds every class in the project that directly subclasses `item`.
+pub fn subtypes(db: &dyn Db, item: &TypeHierarchyItem) -> Vec<TypeHierarchyItem> {
+    let mut subtypes = Vec::new();
+
+    for file in &db.project().files(db) {
+        let source = source_text(db, *file);
+        if !source.as_str().contains(item.name.as_str()) {
+            continue;
+        }
+
+        let module = parsed_module(db, *file).load(db);
+
+        for class in class_defs(&module) {
+            let Some(arguments) = class.arguments.as_deref() else {
+                continue;
+            };
+
+            let is_subtype = arguments.args.iter().any(|base| {
+                let Some(result) = goto_definition(db, *file, base.range().start()) else {
+                    return false;
+                };
+                result.value.into_iter().any(|target| {
+                    target.file() == item.target.file()
+                        && target.full_range() == item.target.full_range()
+                })
+            });
+
+            if is_subtype {
+                subtypes.push(TypeHierarchyItem {
+                    name: class.name.to_string(),
+                    target: NavigationTarget {
+                        file: *file,
+                        focus_range: class.name.range(),
+                        full_range: class.range(),
+                    },
+                });
+            }
+        }
+    }
+
+    subtypes
+}
+
+/// Builds a [`TypeHierarchyItem`] for `target`, provided that it actually points at a class
+/// definition.
+fn type_hierarchy_item_for_target(db: &dyn Db, target: NavigationTarget) -> Option<TypeHierarchyItem> {
+    let module = parsed_module(db, target.file()).load(db);
+    let class = class_def_at(&module, target.full_range())?;
+    Some(TypeHierarchyItem {
+        name: class.name.to_string(),
+        target,
+    })
+}
+
+/// Finds the innermost class definition that covers `range`.
+fn class_def_at<'a>(module: &'a ParsedModuleRef, range: TextRange) -> Option<&'a ast::StmtClassDef> {
+    let root = AnyNodeRef::from(module.syntax());
+    covering_node(root, range).ancestors().find_map(|node| match node {
+        AnyNodeRef::StmtClassDef(class) => Some(class),
+        _ => None,
+    })
+}
+
+/// Collects every class definition in a module, including nested ones.
+fn class_defs(module: &ParsedModuleRef) -> Vec<&ast::StmtClassDef> {
+    let root = AnyNodeRef::from(module.syntax());
+    let mut collector = ClassCollector { classes: Vec::new() };
+    root.visit_source_order(&mut collector);
+    collector.classes
+}
+
+struct ClassCollector<'a> {
+    classes: Vec<&'a ast::StmtClassDef>,
+}
+
+impl<'a> SourceOrderVisitor<'a> for ClassCollector<'a> {
+    fn enter_node(&mut self, node: AnyNodeRef<'a>) -> TraversalSignal {
+        if let AnyNodeRef::StmtClassDef(class) = node {
+            self.classes.push(class);
+        }
+
+        TraversalSignal::Traverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{CursorTest, IntoDiagnostic, cursor_test};
+    use insta::assert_snapshot;
+    use ruff_db::diagnostic::{Annotation, Diagnostic, DiagnosticId, LintName, Severity, Span};
+
+    impl CursorTest {
+        fn prepare_type_hierarchy(&self) -> String {
+            let Some(items) =
+                prepare_type_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No type hierarchy item found".to_string();
+            };
+
+            self.render_diagnostics(items.into_iter().map(TypeHierarchyItemResult))
+        }
+
+        fn supertypes(&self) -> String {
+            let Some(items) =
+                prepare_type_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No type hierarchy item found".to_string();
+            };
+
+            let supertypes: Vec<_> = items
+                .iter()
+                .flat_map(|item| supertypes(&self.db, item))
+                .collect();
+
+            if supertypes.is_empty() {
+                return "No supertypes found".to_string();
+            }
+
+            self.render_diagnostics(supertypes.into_iter().map(TypeHierarchyItemResult))
+        }
+
+        fn subtypes(&self) -> String {
+            let Some(items) =
+                prepare_type_hierarchy(&self.db, self.cursor.file, self.cursor.offset)
+            else {
+                return "No type hierarchy item found".to_string();
+            };
+
+            let subtypes: Vec<_> = items
+                .iter()
+                .flat_map(|item| subtypes(&self.db, item))
+                .collect();
+
+            if subtypes.is_empty() {
+                return "No subtypes found".to_string();
+            }
+
+            self.render_diagnostics(subtypes.into_iter().map(TypeHierarchyItemResult))
+        }
+    }
+
+    struct TypeHierarchyItemResult(TypeHierarchyItem);
+
+    impl IntoDiagnostic for TypeHierarchyItemResult {
+        fn into_diagnostic(self) -> Diagnostic {
+            let mut main = Diagnostic::new(
+                DiagnosticId::Lint(LintName::of("type-hierarchy")),
+                Severity::Info,
+                format!("Type hierarchy item `{}`", self.0.name),
+            );
+            main.annotate(Annotation::primary(
+                Span::from(self.0.target.file()).with_range(self.0.target.focus_range()),
+            ));
+            main
+        }
+    }
+
+    #[test]
+    fn prepare_on_class_definition() {
+        let test = cursor_test(
+            "
+class An<CURSOR>imal:
+    pass
+",
+        );
+
+        assert_snapshot!(test.prepare_type_hierarchy(), @r"
+        info[type-hierarchy]: Type hierarchy item `Animal`
+         --> main.py:2:7
+          |
+        2 | class Animal:
+          |       ^^^^^^
+        3 |     pass
+          |
+        ");
+    }
+
+    #[test]
+    fn supertypes_across_files() {
+        let test = CursorTest::builder()
+            .source(
+                "animals.py",
+                "
+class Animal:
+    pass
+
+class Pet:
+    pass
+",
+            )
+            .source(
+                "main.py",
+                "
+from animals import Animal, Pet
+
+class D<CURSOR>og(Animal, Pet):
+    pass
+",
+            )
+            .build();
+
+        assert_snapshot!(test.supertypes(), @r"
+        info[type-hierarchy]: Type hierarchy item `Animal`
+         --> animals.py:2:7
+          |
+        2 | class Animal:
+          |       ^^^^^^
+        3 |     pass
+          |
+
+        info[type-hierarchy]: Type hierarchy item `Pet`
+         --> animals.py:5:7
+          |
+        5 | class Pet:
+          |       ^^^
+        6 |     pass
+          |
+        ");
+    }
+
+    #[test]
+    fn subtypes_across_files() {
+        let test = CursorTest::builder()
+            .source(
+                "animals.py",
+                "
+class An<CURSOR>imal:
+    pass
+",
+            )
+            .source(
+                "main.py",
+                "
+from animals import Animal
+
+class Dog(Animal):
+    pass
+
+class Cat(Animal):
+    pass
+
+class Unrelated:
+    animal_name = 'Animal'
+",
+            )
+            .build();
+
+        assert_snapshot!(test.subtypes(), @r"
+        info[type-hierarchy]: Type hierarchy item `Dog`
+         --> main.py:4:7
+          |
+        4 | class Dog(Animal):
+          |       ^^^
+        5 |     pass
+          |
+
+        info[type-hierarchy]: Type hierarchy item `Cat`
+         --> main.py:7:7
+          |
+        7 | class Cat(Animal):
+          |       ^^^
+        8 |     pass
+          |
+        ");
+    }
+}
@@ -67,9 +67,18 @@ impl ProjectDatabase {
             system: Arc::new(system),
         };
 
-        // TODO: Use the `program_settings` to compute the key for the database's persistent
-        //   cache and load the cache if it exists.
-        //   we may want to have a dedicated method for this?
+        // There's no persistent, on-disk cache for module resolution tables, stub digests, or
+        // the symbol index yet: `salsa::Storage` in the revision this crate is pinned to doesn't
+        // support serializing its query results, so there would be nothing to load even if we
+        // wrote one, and a cache keyed loosely enough to work around that (e.g. by file mtimes)
+        // risks silently serving stale types to the editor, which is worse than a cold start.
+        // We do, however, compute and log the half of that problem that's safe to solve now:
+        // a fingerprint of the resolved configuration, so a future on-disk cache doesn't have to
+        // reinvent "did this project's settings change since the last run".
+        tracing::debug!(
+            "Project configuration fingerprint: {:016x}",
+            configuration_fingerprint(&project_metadata)
+        );
 
         // Initialize the `Program` singleton
         let program_settings = project_metadata.to_program_settings(db.system(), db.vendored())?;
@@ -245,6 +254,20 @@ fn bytes_to_mb(total: usize) -> f64 {
     total as f64 / 1_000_000.
 }
 
+/// Computes a fingerprint for `metadata`'s resolved configuration.
+///
+/// `ProjectMetadata` and the `Options` it wraps don't implement [`Hash`](std::hash::Hash)
+/// (some of their fields, like path globs, don't either), so this hashes their `Debug`
+/// representation instead. That's fine here: the fingerprint only needs to change whenever the
+/// resolved configuration does, not to be a cryptographically strong digest.
+fn configuration_fingerprint(metadata: &ProjectMetadata) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = rustc_hash::FxHasher::default();
+    format!("{metadata:?}").hash(&mut hasher);
+    hasher.finish()
+}
+
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 impl SalsaMemoryDump {
     /// Returns a short report that provides total memory usage information.
@@ -659,4 +682,25 @@ pub(crate) mod tests {
 
     #[salsa::db]
     impl salsa::Database for TestDb {}
+
+    #[test]
+    fn configuration_fingerprint_is_stable_and_detects_changes() {
+        use ruff_python_ast::name::Name;
+        use ruff_db::system::SystemPathBuf;
+
+        use super::configuration_fingerprint;
+
+        let unchanged = ProjectMetadata::new(Name::new_static("test"), SystemPathBuf::from("/"));
+        assert_eq!(
+            configuration_fingerprint(&unchanged),
+            configuration_fingerprint(&unchanged)
+        );
+
+        let different_root =
+            ProjectMetadata::new(Name::new_static("test"), SystemPathBuf::from("/other"));
+        assert_ne!(
+            configuration_fingerprint(&unchanged),
+            configuration_fingerprint(&different_root)
+        );
+    }
 }
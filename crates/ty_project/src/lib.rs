@@ -801,4 +801,34 @@ mod tests {
 
         Ok(())
     }
+
+    /// In `OpenFiles` mode (used for single-file editors), checking an open file
+    /// must not trigger a full indexing walk of the project: large monorepos would otherwise pay
+    /// for crawling the entire workspace just to check the one file the user has open.
+    #[test]
+    fn should_check_file_does_not_index_project_in_open_files_mode() -> ruff_db::system::Result<()>
+    {
+        let project_metadata =
+            ProjectMetadata::new(Name::new_static("test"), SystemPathBuf::from("/"));
+        let mut db = TestDb::new(project_metadata);
+        db.init_program().unwrap();
+
+        db.write_file("test.py", "x = 10")?;
+        db.write_file("other.py", "y = 20")?;
+        let file = system_path_to_file(&db, "test.py").unwrap();
+
+        let project = db.project();
+        project
+            .set_check_mode(&mut db)
+            .to(crate::CheckMode::OpenFiles);
+        project.open_file(&mut db, file);
+
+        assert!(project.should_check_file(&db, file));
+        assert!(
+            project.file_set(&db).is_lazy(),
+            "checking an open file shouldn't index the rest of the project"
+        );
+
+        Ok(())
+    }
 }
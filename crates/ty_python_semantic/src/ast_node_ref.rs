@@ -117,6 +117,31 @@ unsafe impl<T> salsa::Update for AstNodeRef<T> {
 
 impl<T> get_size2::GetSize for AstNodeRef<T> {}
 
+#[cfg(test)]
+mod tests {
+    use ruff_db::files::system_path_to_file;
+    use ruff_db::parsed::parsed_module;
+
+    use super::AstNodeRef;
+    use crate::db::tests::TestDbBuilder;
+
+    #[test]
+    fn node_roundtrips_through_the_same_module() {
+        let db = TestDbBuilder::new()
+            .with_file("/src/foo.py", "class C: ...")
+            .build()
+            .unwrap();
+
+        let foo = system_path_to_file(&db, "/src/foo.py").unwrap();
+        let module = parsed_module(&db, foo).load(&db);
+
+        let class = module.suite()[0].as_class_def_stmt().unwrap();
+        let node_ref = AstNodeRef::new(&module, class);
+
+        assert_eq!(node_ref.node(&module), class);
+    }
+}
+
 #[allow(clippy::missing_fields_in_debug)]
 impl<T> Debug for AstNodeRef<T>
 where
@@ -82,6 +82,22 @@ impl<'db> Module<'db> {
         }
     }
 
+    /// Returns a short, human-readable description of what kind of search path this module was
+    /// resolved from, e.g. `"site-packages"`, `"first-party code"`, or `"stdlib typeshed stubs
+    /// vendored by ty"`.
+    ///
+    /// This is the same provenance description ty's own diagnostics use; it's exposed here so a
+    /// caller that already has a resolved [`Module`] (e.g. from [`SemanticModel::resolve_module`])
+    /// can show the user where an import came from without reaching into the module resolver's
+    /// internals itself.
+    ///
+    /// Returns `None` for a namespace package, matching [`Module::search_path`].
+    ///
+    /// [`SemanticModel::resolve_module`]: crate::SemanticModel::resolve_module
+    pub fn search_path_kind(self, db: &'db dyn Database) -> Option<&'static str> {
+        self.search_path(db).map(SearchPath::describe_kind)
+    }
+
     /// Determine whether this module is a single-file module or a package
     pub fn kind(self, db: &'db dyn Database) -> ModuleKind {
         match self {
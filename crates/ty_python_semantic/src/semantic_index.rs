@@ -51,6 +51,17 @@ pub(crate) use self::use_def::{
 /// Returns the semantic index for `file`.
 ///
 /// Prefer using [`symbol_table`] when working with symbols from a single scope.
+///
+/// This query is `no_eq`, so it always reports as changed and re-triggers on any edit to `file`:
+/// rebuilding the scope tree requires a full AST walk, so there's no cheaper way to detect "this
+/// edit didn't actually change the index" at this level. That doesn't mean a one-line edit inside
+/// a single function re-infers the whole file, though: [`place_table`] and [`use_def_map`] (and,
+/// one layer up, [`crate::types::infer_definition_types`] and [`crate::types::infer_scope_types`])
+/// are tracked per-scope/per-definition rather than per-file, and Salsa backdates each of those to
+/// "unchanged" when the rebuilt value for its scope compares equal to the last one. So an edit
+/// confined to one function's body only invalidates that function's own place table, use-def map,
+/// and inferred types; sibling functions and the rest of the file are recomputed here (cheaply,
+/// since this is just indexing, not type inference) but their dependents are not.
 #[salsa::tracked(returns(ref), no_eq, heap_size=ruff_memory_usage::heap_size)]
 pub(crate) fn semantic_index(db: &dyn Db, file: File) -> SemanticIndex<'_> {
     let _span = tracing::trace_span!("semantic_index", ?file).entered();
@@ -7,8 +7,10 @@ use ruff_source_file::LineIndex;
 use rustc_hash::FxHashMap;
 
 use crate::module_name::ModuleName;
-use crate::module_resolver::{KnownModule, Module, list_modules, resolve_module};
+use crate::module_resolver::{KnownModule, Module, file_to_module, list_modules, resolve_module};
+use crate::place::{ConsideredDefinitions, builtins_symbol, symbol};
 use crate::semantic_index::definition::Definition;
+use crate::semantic_index::place_table;
 use crate::semantic_index::scope::FileScopeId;
 use crate::semantic_index::semantic_index;
 use crate::types::list_members::{Member, all_members, all_reachable_members};
@@ -55,6 +57,11 @@ impl<'db> SemanticModel<'db> {
         self.file.path(self.db)
     }
 
+    /// Returns the [`Module`] that this file belongs to, if any.
+    pub fn module(&self) -> Option<Module<'db>> {
+        file_to_module(self.db, self.file)
+    }
+
     pub fn line_index(&self) -> LineIndex {
         line_index(self.db, self.file)
     }
@@ -164,7 +171,12 @@ impl<'db> SemanticModel<'db> {
         let builtin = module.is_known(self.db, KnownModule::Builtins);
 
         let mut completions = vec![];
-        for Member { name, ty } in all_members(self.db, ty) {
+        for Member {
+            name,
+            ty,
+            defining_class: _,
+        } in all_members(self.db, ty)
+        {
             completions.push(Completion {
                 name,
                 ty: Some(ty),
@@ -210,6 +222,101 @@ impl<'db> SemanticModel<'db> {
             .collect()
     }
 
+    /// Returns the members of `ty`, honoring the MRO and metaclass attributes.
+    ///
+    /// This is the same enumeration `attribute_completions` is built on, exposed directly for
+    /// callers that need the full [`Member`] (including its `defining_class`) rather than a
+    /// completion-shaped projection of it.
+    pub fn members_of(&self, ty: Type<'db>) -> Vec<Member<'db>> {
+        all_members(self.db, ty).into_iter().collect()
+    }
+
+    /// Resolves `name` by walking the scope chain starting at `scope`: the scope itself, then
+    /// each enclosing scope, then the module's global scope, then builtins.
+    ///
+    /// This is the same chain `scoped_completions` enumerates, narrowed to a single name instead
+    /// of listing everything reachable.
+    ///
+    /// Returns the resolved type, or `None` if `name` isn't bound anywhere in the chain.
+    pub fn lookup_symbol(&self, name: &str, scope: FileScopeId) -> Option<Type<'db>> {
+        let index = semantic_index(self.db, self.file);
+
+        for (file_scope, _) in index.ancestor_scopes(scope) {
+            let place = symbol(
+                self.db,
+                file_scope.to_scope_id(self.db, self.file),
+                name,
+                ConsideredDefinitions::AllReachable,
+            );
+            if let Some(ty) = place.ignore_possibly_undefined() {
+                return Some(ty);
+            }
+        }
+
+        builtins_symbol(self.db, name).ignore_possibly_undefined()
+    }
+
+    /// Returns the names of the symbols declared directly in `scope`, without walking any
+    /// enclosing scopes or builtins.
+    ///
+    /// This is the raw symbol table `lookup_symbol` and `scoped_completions` both consult one
+    /// scope at a time while walking the scope chain; exposed directly for callers (e.g. a
+    /// request handler that already has a scope from [`SemanticModel::scope`]) that want the
+    /// names in a single scope rather than a resolved type or a chain-wide completion list.
+    pub fn symbol_names_in_scope(&self, scope: FileScopeId) -> Vec<Name> {
+        place_table(self.db, scope.to_scope_id(self.db, self.file))
+            .symbols()
+            .map(|symbol| symbol.name().clone())
+            .collect()
+    }
+
+    /// Returns the flow-sensitive (narrowed) type of `node` at its use site, for whichever kind
+    /// of expression node it turns out to be.
+    ///
+    /// This is the same per-node narrowing [`HasType::inferred_type`] already provides for a
+    /// specific expression type (a name, an attribute path, a subscript, ...); this wrapper lets
+    /// callers holding an [`ast::AnyNodeRef`] (e.g. from [`SemanticModel::scope`] or
+    /// [`ruff_python_ast::find_node::covering_node`]) query it without first matching on the
+    /// node's concrete variant themselves.
+    ///
+    /// Returns `None` if `node` isn't an expression, or if it isn't bound to a known type.
+    pub fn narrowed_type_at(&self, node: ast::AnyNodeRef<'_>) -> Option<Type<'db>> {
+        node.as_expr_ref()?.inferred_type(self)
+    }
+
+    /// Parses `source` as a standalone expression and infers its type, resolving any free names
+    /// against the scope containing `anchor` (via [`SemanticModel::lookup_symbol`]).
+    ///
+    /// This only supports the subset of expression syntax needed to resolve a name or a chain of
+    /// attribute accesses rooted at one, e.g. `foo`, `foo.bar`, or `foo.bar.baz`. Returns `None`
+    /// if `source` doesn't parse as a single expression, if `anchor`'s scope can't be determined,
+    /// or if evaluation hits unsupported syntax.
+    pub fn evaluate_expression(
+        &self,
+        source: &str,
+        anchor: ast::AnyNodeRef<'_>,
+    ) -> Option<Type<'db>> {
+        let parsed = ruff_python_parser::parse_expression(source).ok()?;
+        let scope = self.scope(anchor)?;
+        self.evaluate_expr(&parsed.syntax().body, scope)
+    }
+
+    fn evaluate_expr(&self, expr: &Expr, scope: FileScopeId) -> Option<Type<'db>> {
+        match expr {
+            Expr::Name(name) => self.lookup_symbol(name.id.as_str(), scope),
+            Expr::Attribute(attribute) => {
+                let value_ty = self.evaluate_expr(&attribute.value, scope)?;
+                crate::types::ide_support::attribute_type(
+                    self.db,
+                    value_ty,
+                    attribute.attr.as_str(),
+                    ast::ExprContext::Load,
+                )
+            }
+            _ => None,
+        }
+    }
+
     /// Returns completions for symbols available in the scope containing the
     /// given expression.
     ///
@@ -276,6 +383,18 @@ impl<'db> SemanticModel<'db> {
             ast::AnyNodeRef::TypeParamTypeVar(var) => {
                 Some(var.definition(self).scope(self.db).file_scope_id(self.db))
             }
+            ast::AnyNodeRef::TypeParamParamSpec(param_spec) => Some(
+                param_spec
+                    .definition(self)
+                    .scope(self.db)
+                    .file_scope_id(self.db),
+            ),
+            ast::AnyNodeRef::TypeParamTypeVarTuple(type_var_tuple) => Some(
+                type_var_tuple
+                    .definition(self)
+                    .scope(self.db)
+                    .file_scope_id(self.db),
+            ),
 
             // Fallback
             node => match node.as_expr_ref() {
@@ -574,6 +693,8 @@ impl_binding_has_ty_def!(ast::Parameter);
 impl_binding_has_ty_def!(ast::ParameterWithDefault);
 impl_binding_has_ty_def!(ast::ExceptHandlerExceptHandler);
 impl_binding_has_ty_def!(ast::TypeParamTypeVar);
+impl_binding_has_ty_def!(ast::TypeParamParamSpec);
+impl_binding_has_ty_def!(ast::TypeParamTypeVarTuple);
 
 impl HasType for ast::Alias {
     fn inferred_type<'db>(&self, model: &SemanticModel<'db>) -> Option<Type<'db>> {
@@ -665,4 +786,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn param_spec_and_type_var_tuple_scope_are_not_global() -> anyhow::Result<()> {
+        let db = TestDbBuilder::new()
+            .with_file("/src/foo.py", "def f[*Ts, **P](*args: *Ts) -> None: ...")
+            .build()?;
+
+        let foo = system_path_to_file(&db, "/src/foo.py").unwrap();
+
+        let ast = parsed_module(&db, foo).load(&db);
+
+        let function = ast.suite()[0].as_function_def_stmt().unwrap();
+        let type_params = function.type_params.as_ref().unwrap();
+
+        let type_var_tuple = type_params.type_params[0].as_type_var_tuple().unwrap();
+        let param_spec = type_params.type_params[1].as_param_spec().unwrap();
+
+        let model = SemanticModel::new(&db, foo);
+
+        assert!(!model.scope(type_var_tuple.into()).unwrap().is_global());
+        assert!(!model.scope(param_spec.into()).unwrap().is_global());
+
+        Ok(())
+    }
 }
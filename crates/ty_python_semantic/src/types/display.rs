@@ -41,6 +41,9 @@ pub struct DisplaySettings<'db> {
     pub qualified: Rc<FxHashMap<&'db str, QualificationLevel>>,
     /// Whether long unions and literals are displayed in full
     pub preserve_full_unions: bool,
+    /// Overrides the default number of union members shown before the rest are
+    /// elided as `... omitted N union elements`. Has no effect when `preserve_full_unions` is set.
+    pub max_union_members: Option<usize>,
     /// Disallow Signature printing to introduce a name
     /// (presumably because we rendered one already)
     pub disallow_signature_name: bool,
@@ -87,6 +90,45 @@ impl<'db> DisplaySettings<'db> {
         }
     }
 
+    #[must_use]
+    pub fn with_max_union_members(self, max: usize) -> Self {
+        Self {
+            max_union_members: Some(max),
+            ..self
+        }
+    }
+
+    /// Forces every class name reachable from `types` to be displayed fully qualified
+    /// (`module.ClassName`), regardless of whether its unqualified name would be ambiguous.
+    ///
+    /// Shares the same traversal and `qualified` machinery as
+    /// [`DisplaySettings::from_possibly_ambiguous_types`], which only qualifies names when needed
+    /// to disambiguate them.
+    #[must_use]
+    pub fn fully_qualified<I, T>(db: &'db dyn Db, types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Type<'db>>,
+    {
+        let collector = AmbiguousClassCollector::default();
+
+        for ty in types {
+            collector.visit_type(db, ty.into());
+        }
+
+        Self {
+            qualified: Rc::new(
+                collector
+                    .class_names
+                    .borrow()
+                    .keys()
+                    .map(|name| (*name, QualificationLevel::ModuleName))
+                    .collect(),
+            ),
+            ..Self::default()
+        }
+    }
+
     #[must_use]
     pub fn from_possibly_ambiguous_types<I, T>(db: &'db dyn Db, types: I) -> Self
     where
@@ -2015,8 +2057,14 @@ impl<'db> FmtDetailed<'db> for DisplayUnionType<'_, 'db> {
         // Done manually because we have a mix of FmtDetailed and Display
         let mut join = f.join(" | ");
 
-        let display_limit =
-            UNION_POLICY.display_limit(total_entries, self.settings.preserve_full_unions);
+        let policy = match self.settings.max_union_members {
+            Some(max) => TruncationPolicy {
+                max,
+                max_when_elided: max,
+            },
+            None => UNION_POLICY,
+        };
+        let display_limit = policy.display_limit(total_entries, self.settings.preserve_full_unions);
 
         let mut condensed_types = Some(condensed_types);
         let mut displayed_entries = 0usize;
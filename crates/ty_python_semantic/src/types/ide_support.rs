@@ -4,14 +4,23 @@ use crate::FxIndexSet;
 use crate::place::builtins_module_scope;
 use crate::semantic_index::definition::Definition;
 use crate::semantic_index::definition::DefinitionKind;
-use crate::semantic_index::{attribute_scopes, global_scope, semantic_index, use_def_map};
+use crate::semantic_index::{
+    FileScopeId, attribute_scopes, global_scope, semantic_index, use_def_map,
+};
 use crate::types::call::{CallArguments, MatchedArgument};
-use crate::types::signatures::{ParameterKind, Signature};
+use crate::types::class::ClassLiteral;
+use crate::types::function::{FunctionType, is_implicit_classmethod, is_implicit_staticmethod};
+use crate::types::generics::typing_self;
+use crate::types::infer::infer_scope_types;
+use crate::types::signatures::{ParameterKind, Parameters, Signature};
+use crate::types::tuple::Tuple;
 use crate::types::{
-    CallDunderError, CallableTypes, ClassBase, KnownUnion, Type, TypeContext, UnionType,
+    CallDunderError, CallableTypes, ClassBase, KnownClass, KnownUnion, SubclassOfInner,
+    SubclassOfType, Type, TypeAliasType, TypeContext, UnionType, declaration_type,
+    infer_definition_types,
 };
 use crate::{Db, DisplaySettings, HasType, SemanticModel};
-use ruff_db::files::FileRange;
+use ruff_db::files::{File, FileRange};
 use ruff_db::parsed::parsed_module;
 use ruff_python_ast::{self as ast, AnyNodeRef};
 use ruff_text_size::{Ranged, TextRange};
@@ -823,6 +832,389 @@ fn find_parameter_range(parameters: &ast::Parameters, parameter_name: &str) -> O
         .map(|param| param.parameter.name.range())
 }
 
+/// Returns the concrete type arguments of a specialized class instance or generic alias, e.g.
+/// `[int, str]` for a value of type `dict[int, str]`, or for the generic alias value
+/// `dict[int, str]` itself (as in `x: type[dict[int, str]]` or a bare `Dict[str, int]` reference).
+///
+/// Returns `None` if `ty` isn't one of those two shapes, or if the class isn't specialized (e.g.
+/// a bare, unsubscripted `dict`).
+pub fn specialization_arguments<'db>(db: &'db dyn Db, ty: Type<'db>) -> Option<Vec<Type<'db>>> {
+    let generic = match ty {
+        Type::NominalInstance(nominal) => nominal.class(db).into_generic_alias()?,
+        Type::GenericAlias(generic) => generic,
+        _ => return None,
+    };
+
+    Some(generic.specialization(db).types(db).to_vec())
+}
+
+/// The element specification of a tuple type: a fixed-length prefix, an optional variadic
+/// "middle" element repeated zero or more times, and a fixed-length suffix.
+///
+/// For a fixed-length tuple like `tuple[int, str]`, `variadic` is `None` and `prefix` holds all of
+/// the element types, with `suffix` empty. For a variable-length tuple like
+/// `tuple[int, *tuple[str, ...], bytes]`, `prefix` is `[int]`, `variadic` is `Some(str)`, and
+/// `suffix` is `[bytes]`.
+pub struct TupleElements<'db> {
+    pub prefix: Vec<Type<'db>>,
+    pub variadic: Option<Type<'db>>,
+    pub suffix: Vec<Type<'db>>,
+}
+
+/// Returns the element specification of a tuple type.
+///
+/// Returns `None` if `ty` is not a tuple instance.
+pub fn tuple_elements<'db>(db: &'db dyn Db, ty: Type<'db>) -> Option<TupleElements<'db>> {
+    let Type::NominalInstance(nominal) = ty else {
+        return None;
+    };
+
+    let tuple_spec = nominal.tuple_spec(db)?;
+
+    Some(match &*tuple_spec {
+        Tuple::Fixed(fixed) => TupleElements {
+            prefix: fixed.elements().copied().collect(),
+            variadic: None,
+            suffix: Vec::new(),
+        },
+        Tuple::Variable(variable) => TupleElements {
+            prefix: variable.prefix_elements().copied().collect(),
+            variadic: Some(variable.variable),
+            suffix: variable.suffix_elements().copied().collect(),
+        },
+    })
+}
+
+/// Resolves the type of `ty.<name>`, performing the same attribute lookup used when inferring
+/// `obj.attr` — including the descriptor protocol and any `__getattr__` fallback.
+///
+/// `access_kind` mirrors the [`ast::ExprContext`] of the attribute expression being resolved.
+/// Only [`ast::ExprContext::Load`] and [`ast::ExprContext::Del`] go through attribute lookup in
+/// this compiler; [`ast::ExprContext::Store`] never reads the existing attribute's type, and
+/// [`ast::ExprContext::Invalid`] corresponds to a parse error, so both return `None` without
+/// performing a lookup.
+///
+/// Returns `None` if the attribute doesn't resolve to a defined member.
+pub fn attribute_type<'db>(
+    db: &'db dyn Db,
+    ty: Type<'db>,
+    name: &str,
+    access_kind: ast::ExprContext,
+) -> Option<Type<'db>> {
+    match access_kind {
+        ast::ExprContext::Load | ast::ExprContext::Del => {
+            ty.member(db, name).ignore_possibly_undefined()
+        }
+        ast::ExprContext::Store | ast::ExprContext::Invalid => None,
+    }
+}
+
+/// The reason applying explicit type arguments to a generic class failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecializationError {
+    /// `class` isn't generic, so it doesn't accept type arguments at all.
+    NotGeneric,
+    /// The number of type arguments doesn't match the number of type parameters.
+    ArityMismatch { expected: usize, actual: usize },
+}
+
+/// Applies `type_arguments` to `class`, producing the specialized class type, e.g. applying
+/// `[int, str]` to `dict` to obtain `dict[int, str]`.
+///
+/// Returns `Err` if `class` isn't generic, or if the number of type arguments doesn't match the
+/// number of type parameters. This only validates arity; per-parameter bound and constraint
+/// violations are not reported here (the normal subscript-expression inference path reports those
+/// as diagnostics when a user writes `Class[...]` directly in source).
+pub fn apply_specialization<'db>(
+    db: &'db dyn Db,
+    class: ClassLiteral<'db>,
+    type_arguments: Vec<Type<'db>>,
+) -> Result<Type<'db>, SpecializationError> {
+    let Some(generic_context) = class.generic_context(db) else {
+        return Err(SpecializationError::NotGeneric);
+    };
+
+    let expected = generic_context.len(db);
+    let actual = type_arguments.len();
+    if expected != actual {
+        return Err(SpecializationError::ArityMismatch { expected, actual });
+    }
+
+    let specialization = generic_context.specialize(db, type_arguments.into_boxed_slice());
+    Ok(Type::from(
+        class.apply_specialization(db, |_| specialization),
+    ))
+}
+
+/// The declaration site of a type's underlying class, function, or type alias.
+pub struct DeclarationSite<'db> {
+    pub definition: Definition<'db>,
+    pub range: FileRange,
+    pub is_stub: bool,
+}
+
+/// Returns the declaration site of `ty`'s underlying class, function, or type alias.
+///
+/// Returns `None` for types with no single declaring definition, e.g. dynamic types, literals, or
+/// unions/intersections (callers that want per-member sites should decompose those themselves and
+/// call this function on each element).
+pub fn declaration_site<'db>(db: &'db dyn Db, ty: Type<'db>) -> Option<DeclarationSite<'db>> {
+    let definition = match ty {
+        Type::ClassLiteral(class) => class.definition(db),
+        Type::GenericAlias(generic) => generic.origin(db).definition(db),
+        Type::FunctionLiteral(function) => function.definition(db),
+        Type::TypeAlias(TypeAliasType::PEP695(alias)) => alias.definition(db),
+        Type::TypeAlias(TypeAliasType::ManualPEP695(alias)) => alias.definition(db)?,
+        _ => return None,
+    };
+
+    let file = definition.file(db);
+    let module = parsed_module(db, file).load(db);
+
+    Some(DeclarationSite {
+        definition,
+        range: definition.focus_range(db, &module),
+        is_stub: file.is_stub(db),
+    })
+}
+
+/// The type of a function both before and after its decorators are applied.
+pub struct DecoratedFunctionType<'db> {
+    /// The function's own type, ignoring any decorators applied to it.
+    pub undecorated: Type<'db>,
+    /// The type callers actually see, i.e. `undecorated` run through the function's decorators
+    /// in order. For an undecorated function this is the same as `undecorated`; for e.g. an
+    /// `@property`-decorated function it's the resulting `property` object.
+    pub decorated: Type<'db>,
+}
+
+/// Returns both the raw and decorator-transformed type of `function`.
+pub fn decorated_function_type<'db>(
+    db: &'db dyn Db,
+    function: FunctionType<'db>,
+) -> DecoratedFunctionType<'db> {
+    let definition = function.definition(db);
+    let decorated = declaration_type(db, definition).inner_type();
+    let undecorated = infer_definition_types(db, definition)
+        .undecorated_type()
+        .unwrap_or(decorated);
+    DecoratedFunctionType {
+        undecorated,
+        decorated,
+    }
+}
+
+/// Returns `true` if `class` is a `Protocol` class (directly or transitively inherits from
+/// `typing.Protocol`).
+pub fn is_protocol_class<'db>(db: &'db dyn Db, class: ClassLiteral<'db>) -> bool {
+    class.is_protocol(db)
+}
+
+/// Returns the names of the members that make up `class`'s protocol interface, i.e. the
+/// attributes, methods, and properties a type must provide to be considered structurally
+/// compatible with `class`.
+///
+/// Returns `None` if `class` isn't a protocol class.
+pub fn protocol_interface_members<'db>(
+    db: &'db dyn Db,
+    class: ClassLiteral<'db>,
+) -> Option<Vec<String>> {
+    let protocol_class = class.into_protocol_class(db)?;
+    Some(
+        protocol_class
+            .interface(db)
+            .members(db)
+            .map(|member| member.name().to_string())
+            .collect(),
+    )
+}
+
+/// A single member of a protocol's interface, paired with whether `ty` provides it.
+pub struct ProtocolMemberConformance {
+    pub name: String,
+    pub is_present: bool,
+}
+
+/// Checks `ty` against each member of `class`'s protocol interface and reports, per member,
+/// whether `ty` provides an attribute, method, or property of that name.
+///
+/// This only checks *presence*, not whether the member's type is actually compatible with what
+/// the protocol requires; a full structural check should instead compare `ty` against
+/// `Type::instance` of the protocol with [`Type::is_assignable_to`], which already implements
+/// the complete per-member relation (including signatures and variance). This function exists to
+/// produce the kind of per-member report a diagnostic or quick-fix wants, which that boolean
+/// result can't give on its own.
+///
+/// Returns `None` if `class` isn't a protocol class.
+pub fn check_protocol_conformance<'db>(
+    db: &'db dyn Db,
+    class: ClassLiteral<'db>,
+    ty: Type<'db>,
+) -> Option<Vec<ProtocolMemberConformance>> {
+    let protocol_class = class.into_protocol_class(db)?;
+    Some(
+        protocol_class
+            .interface(db)
+            .members(db)
+            .map(|member| ProtocolMemberConformance {
+                name: member.name().to_string(),
+                is_present: !ty.member(db, member.name()).is_undefined(),
+            })
+            .collect(),
+    )
+}
+
+/// Returns the inferred (not declared) return type of `function`'s last overload or
+/// implementation, mirroring what its body actually returns rather than what (if anything) its
+/// `->` annotation says.
+///
+/// This is the union of every `return` statement's expression type found directly in the
+/// function's own body (not walking into the bodies of nested functions, lambdas, or classes),
+/// plus `None` if control can implicitly fall off the end of the function. For a generator
+/// function this is instead the appropriate `Generator`/`AsyncGenerator` instance, matching how
+/// [`FunctionType::signature`] treats a generator's declared return type; for a non-generator
+/// `async def` the union is wrapped in `Coroutine[Any, Any, ...]`, reusing the same
+/// [`Signature::wrap_coroutine_return_type`] logic `signature` itself uses.
+///
+/// This exists for callers like an "add return annotation" code action or a stub generator, which
+/// want the type the function's body would actually produce regardless of its current annotation.
+pub fn inferred_return_type<'db>(db: &'db dyn Db, function: FunctionType<'db>) -> Type<'db> {
+    let overload = function.literal(db).last_definition(db);
+    let scope = overload.body_scope(db);
+    let file = scope.file(db);
+    let module = parsed_module(db, file).load(db);
+    let function_node = scope.node(db).expect_function().node(&module);
+    let index = semantic_index(db, file);
+    let file_scope_id = scope.file_scope_id(db);
+
+    if file_scope_id.is_generator_function(index) {
+        let known_class = if function_node.is_async {
+            KnownClass::AsyncGeneratorType
+        } else {
+            KnownClass::GeneratorType
+        };
+        return known_class.to_instance_unknown(db);
+    }
+
+    let mut return_expressions = Vec::new();
+    collect_return_expressions(&function_node.body, &mut return_expressions);
+
+    let mut return_types: Vec<Type<'db>> = return_expressions
+        .into_iter()
+        .map(|expr| infer_scope_types(db, scope).expression_type(expr))
+        .collect();
+
+    if use_def_map(db, scope).can_implicitly_return_none(db) {
+        return_types.push(Type::none(db));
+    }
+
+    let inferred = UnionType::from_elements(db, return_types);
+
+    if function_node.is_async {
+        Signature::new(Parameters::empty(), Some(inferred))
+            .wrap_coroutine_return_type(db)
+            .return_ty
+            .unwrap_or(inferred)
+    } else {
+        inferred
+    }
+}
+
+/// Collects the expressions of every `return` statement directly in `body`, not descending into
+/// the bodies of nested functions, lambdas, or classes (each of those introduces its own scope,
+/// and its `return` statements belong to that scope, not this one).
+fn collect_return_expressions<'a>(body: &'a [ast::Stmt], returns: &mut Vec<&'a ast::Expr>) {
+    for statement in body {
+        match statement {
+            ast::Stmt::Return(ast::StmtReturn {
+                value: Some(value), ..
+            }) => returns.push(value),
+            ast::Stmt::If(node) => {
+                collect_return_expressions(&node.body, returns);
+                for clause in &node.elif_else_clauses {
+                    collect_return_expressions(&clause.body, returns);
+                }
+            }
+            ast::Stmt::Try(node) => {
+                collect_return_expressions(&node.body, returns);
+                for ast::ExceptHandler::ExceptHandler(handler) in &node.handlers {
+                    collect_return_expressions(&handler.body, returns);
+                }
+                collect_return_expressions(&node.orelse, returns);
+                collect_return_expressions(&node.finalbody, returns);
+            }
+            ast::Stmt::With(node) => collect_return_expressions(&node.body, returns),
+            ast::Stmt::For(node) => {
+                collect_return_expressions(&node.body, returns);
+                collect_return_expressions(&node.orelse, returns);
+            }
+            ast::Stmt::While(node) => {
+                collect_return_expressions(&node.body, returns);
+                collect_return_expressions(&node.orelse, returns);
+            }
+            ast::Stmt::Match(node) => {
+                for case in &node.cases {
+                    collect_return_expressions(&case.body, returns);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the type of the implicit `self`/`cls` parameter for the method whose body scope is
+/// `scope`, respecting `Self`, generic classes, and `@classmethod`.
+///
+/// This mirrors the synthesis the type inferrer itself uses for an unannotated `self`/`cls`
+/// parameter: a `Self`-bound instance of the enclosing class for a regular method, or `type[Self]`
+/// for a `@classmethod`. It exists so a caller that already has a method's body scope (e.g. from
+/// [`SemanticModel::scope`]) doesn't have to separately find and type-check that method's first
+/// parameter just to learn what `self`/`cls` refers to.
+///
+/// Returns `None` if `scope` isn't a method's body scope, or the method is a `@staticmethod`
+/// (which has no implicit first parameter).
+pub fn self_or_cls_type<'db>(db: &'db dyn Db, file: File, scope: FileScopeId) -> Option<Type<'db>> {
+    let index = semantic_index(db, file);
+    let class_definition = index.class_definition_of_method(scope)?;
+
+    let scope_id = scope.to_scope_id(db, file);
+    let function_node_ref = scope_id.node(db).as_function()?;
+    let module = parsed_module(db, file).load(db);
+    let function_node = function_node_ref.node(&module);
+
+    if is_implicit_staticmethod(&function_node.name) {
+        return None;
+    }
+
+    let method_definition = index.expect_single_definition(function_node_ref);
+    let mut is_classmethod = is_implicit_classmethod(&function_node.name);
+
+    let inference = infer_definition_types(db, method_definition);
+    for decorator in &function_node.decorator_list {
+        let decorator_ty = inference.expression_type(&decorator.expression);
+        if let Some(known_class) = decorator_ty
+            .as_class_literal()
+            .and_then(|class| class.known(db))
+        {
+            if known_class == KnownClass::Staticmethod {
+                return None;
+            }
+            is_classmethod |= known_class == KnownClass::Classmethod;
+        }
+    }
+
+    let class_literal = declaration_type(db, class_definition)
+        .inner_type()
+        .as_class_literal()?;
+
+    let typing_self = typing_self(db, scope_id, Some(method_definition), class_literal)?;
+    Some(if is_classmethod {
+        SubclassOfType::from(db, SubclassOfInner::TypeVar(typing_self))
+    } else {
+        Type::TypeVar(typing_self)
+    })
+}
+
 mod resolve_definition {
     //! Resolves an Import, `ImportFrom` or `StarImport` definition to one or more
     //! "resolved definitions". This is done recursively to find the original
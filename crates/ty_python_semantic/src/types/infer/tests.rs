@@ -294,6 +294,29 @@ fn first_public_binding<'db>(db: &'db TestDb, file: File, name: &str) -> Definit
         .expect("no binding found")
 }
 
+/// Like [`first_public_binding`], but for `name`'s binding in the named function/class scope
+/// that is a direct child of `file`'s global scope, rather than the global scope itself.
+#[track_caller]
+fn first_binding_in_child_scope<'db>(
+    db: &'db TestDb,
+    file: File,
+    child_scope_name: &str,
+    name: &str,
+) -> Definition<'db> {
+    let module = parsed_module(db, file).load(db);
+    let index = semantic_index(db, file);
+    let (file_scope_id, _) = index
+        .child_scopes(FileScopeId::global())
+        .find(|(scope_id, _)| scope_id.to_scope_id(db, file).name(db, &module) == child_scope_name)
+        .unwrap_or_else(|| panic!("no child scope named {child_scope_name}"));
+    let scope = file_scope_id.to_scope_id(db, file);
+
+    use_def_map(db, scope)
+        .end_of_scope_symbol_bindings(place_table(db, scope).symbol_id(name).unwrap())
+        .find_map(|b| b.binding.definition())
+        .expect("no binding found")
+}
+
 #[test]
 fn dependency_public_symbol_type_change() -> anyhow::Result<()> {
     let mut db = setup_db();
@@ -391,6 +414,58 @@ fn dependency_unrelated_symbol() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Edits confined to one function's body shouldn't cause a sibling function in the same file
+/// to be re-inferred: `place_table`/`use_def_map`/`infer_definition_types` are tracked
+/// per-scope, so Salsa backdates the sibling's query results to "unchanged" rather than
+/// propagating the invalidation past the edited function. See the module docs on
+/// [`crate::semantic_index::semantic_index`] for the full explanation.
+#[test]
+fn dependency_unrelated_sibling_function() -> anyhow::Result<()> {
+    let mut db = setup_db();
+
+    db.write_dedented(
+        "/src/a.py",
+        r#"
+        def foo():
+            x: int = 1
+
+        def bar():
+            y: int = 2
+        "#,
+    )?;
+
+    let x_ty = get_symbol(&db, "/src/a.py", &["foo"], "x").expect_type();
+
+    assert_eq!(x_ty.display(&db).to_string(), "int");
+
+    // Change `bar`'s body; `foo` (and its binding of `x`) is untouched.
+    db.write_dedented(
+        "/src/a.py",
+        r#"
+        def foo():
+            x: int = 1
+
+        def bar():
+            y: bool = True
+        "#,
+    )?;
+
+    let a = system_path_to_file(&db, "/src/a.py").unwrap();
+    let x_definition = first_binding_in_child_scope(&db, a, "foo", "x");
+
+    db.clear_salsa_events();
+
+    let x_ty_2 = get_symbol(&db, "/src/a.py", &["foo"], "x").expect_type();
+
+    assert_eq!(x_ty_2.display(&db).to_string(), "int");
+
+    let events = db.take_salsa_events();
+
+    assert_function_query_was_not_run(&db, infer_definition_types, x_definition, &events);
+
+    Ok(())
+}
+
 #[test]
 fn dependency_implicit_instance_attribute() -> anyhow::Result<()> {
     fn x_rhs_expression(db: &TestDb) -> Expression<'_> {
@@ -366,6 +366,7 @@ impl<'db> AllMembers<'db> {
                     self.members.insert(Member {
                         name: symbol_name.clone(),
                         ty,
+                        defining_class: None,
                     });
                 }
 
@@ -374,7 +375,11 @@ impl<'db> AllMembers<'db> {
                         |submodule_name| {
                             let ty = literal.resolve_submodule(db, &submodule_name)?;
                             let name = submodule_name.clone();
-                            Some(Member { name, ty })
+                            Some(Member {
+                                name,
+                                ty,
+                                defining_class: None,
+                            })
                         },
                     ));
             }
@@ -422,6 +427,7 @@ impl<'db> AllMembers<'db> {
                 self.members.insert(Member {
                     name: memberdef.member.name,
                     ty,
+                    defining_class: Some(parent),
                 });
             }
         }
@@ -453,6 +459,7 @@ impl<'db> AllMembers<'db> {
                     self.members.insert(Member {
                         name: Name::new(name),
                         ty,
+                        defining_class: Some(parent),
                     });
                 }
             }
@@ -470,6 +477,7 @@ impl<'db> AllMembers<'db> {
                 self.members.insert(Member {
                     name: memberdef.member.name,
                     ty,
+                    defining_class: Some(parent),
                 });
             }
         }
@@ -497,6 +505,7 @@ impl<'db> AllMembers<'db> {
                         self.members.insert(Member {
                             name: Name::from(*attr),
                             ty: synthetic_member,
+                            defining_class: Some(class_literal),
                         });
                     }
                 }
@@ -530,6 +539,10 @@ pub struct MemberWithDefinition<'db> {
 pub struct Member<'db> {
     pub name: Name,
     pub ty: Type<'db>,
+    /// The class in whose body this member is defined, for members inherited through the MRO or
+    /// synthesized for a class (e.g. a dataclass's generated fields). `None` for members that
+    /// aren't associated with a particular class, such as module-level symbols.
+    pub defining_class: Option<ClassLiteral<'db>>,
 }
 
 impl std::hash::Hash for Member<'_> {
@@ -1,12 +1,15 @@
 use lsp_types::{
-    self as types, ClientCapabilities, CodeActionKind, CodeActionOptions, CompletionOptions,
-    DeclarationCapability, DiagnosticOptions, DiagnosticServerCapabilities,
-    HoverProviderCapability, InlayHintOptions, InlayHintServerCapabilities, MarkupKind,
-    NotebookCellSelector, NotebookSelector, OneOf, RenameOptions, SelectionRangeProviderCapability,
-    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
-    SemanticTokensServerCapabilities, ServerCapabilities, SignatureHelpOptions,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    TypeDefinitionProviderCapability, WorkDoneProgressOptions,
+    self as types, CallHierarchyServerCapability, ClientCapabilities, CodeActionKind,
+    CodeActionOptions, CodeLensOptions, CompletionOptions, DeclarationCapability,
+    DiagnosticOptions, DiagnosticServerCapabilities, DocumentLinkOptions, FileOperationFilter,
+    FileOperationPattern, FileOperationPatternKind, FileOperationRegistrationOptions,
+    HoverProviderCapability, InlayHintOptions, InlayHintServerCapabilities,
+    LinkedEditingRangeServerCapabilities, MarkupKind, NotebookCellSelector, NotebookSelector,
+    OneOf, RenameOptions, SelectionRangeProviderCapability, SemanticTokensFullOptions,
+    SemanticTokensLegend, SemanticTokensOptions, SemanticTokensServerCapabilities,
+    ServerCapabilities, SignatureHelpOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TypeDefinitionProviderCapability, TypeHierarchyServerCapability,
+    WorkDoneProgressOptions, WorkspaceFileOperationsServerCapabilities, WorkspaceServerCapabilities,
 };
 use std::str::FromStr;
 
@@ -52,6 +55,9 @@ impl std::fmt::Display for ResolvedClientCapabilities {
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum SupportedCommand {
     Debug,
+    RestartServer,
+    DumpTypesForFile,
+    ClearCaches,
 }
 
 impl SupportedCommand {
@@ -59,12 +65,20 @@ impl SupportedCommand {
     const fn identifier(self) -> &'static str {
         match self {
             SupportedCommand::Debug => "ty.printDebugInformation",
+            SupportedCommand::RestartServer => "ty.restartServer",
+            SupportedCommand::DumpTypesForFile => "ty.dumpTypesForFile",
+            SupportedCommand::ClearCaches => "ty.clearCaches",
         }
     }
 
     /// Returns all the commands that the server currently supports.
-    const fn all() -> [SupportedCommand; 1] {
-        [SupportedCommand::Debug]
+    const fn all() -> [SupportedCommand; 4] {
+        [
+            SupportedCommand::Debug,
+            SupportedCommand::RestartServer,
+            SupportedCommand::DumpTypesForFile,
+            SupportedCommand::ClearCaches,
+        ]
     }
 }
 
@@ -74,6 +88,9 @@ impl FromStr for SupportedCommand {
     fn from_str(name: &str) -> anyhow::Result<Self, Self::Err> {
         Ok(match name {
             "ty.printDebugInformation" => Self::Debug,
+            "ty.restartServer" => Self::RestartServer,
+            "ty.dumpTypesForFile" => Self::DumpTypesForFile,
+            "ty.clearCaches" => Self::ClearCaches,
             _ => return Err(anyhow::anyhow!("Invalid command `{name}`")),
         })
     }
@@ -364,7 +381,10 @@ pub(crate) fn server_capabilities(
         position_encoding: Some(position_encoding.into()),
         code_action_provider: Some(types::CodeActionProviderCapability::Options(
             CodeActionOptions {
-                code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                code_action_kinds: Some(vec![
+                    CodeActionKind::QUICKFIX,
+                    CodeActionKind::REFACTOR_REWRITE,
+                ]),
                 ..CodeActionOptions::default()
             },
         )),
@@ -391,7 +411,13 @@ pub(crate) fn server_capabilities(
         declaration_provider: Some(DeclarationCapability::Simple(true)),
         references_provider: Some(OneOf::Left(true)),
         rename_provider: Some(OneOf::Right(server_rename_options())),
+        document_link_provider: Some(DocumentLinkOptions {
+            resolve_provider: Some(false),
+            work_done_progress_options: WorkDoneProgressOptions::default(),
+        }),
         document_highlight_provider: Some(OneOf::Left(true)),
+        moniker_provider: Some(OneOf::Left(true)),
+        linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
         signature_help_provider: Some(SignatureHelpOptions {
             trigger_characters: Some(vec!["(".to_string(), ",".to_string()]),
@@ -415,16 +441,38 @@ pub(crate) fn server_capabilities(
                         .collect(),
                 },
                 range: Some(true),
-                full: Some(SemanticTokensFullOptions::Bool(true)),
+                full: Some(SemanticTokensFullOptions::Delta { delta: Some(true) }),
             },
         )),
         completion_provider: Some(CompletionOptions {
             trigger_characters: Some(vec!['.'.to_string()]),
+            resolve_provider: Some(true),
             ..Default::default()
         }),
+        code_lens_provider: Some(CodeLensOptions {
+            resolve_provider: Some(true),
+        }),
         selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+        call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+        type_hierarchy_provider: Some(TypeHierarchyServerCapability::Simple(true)),
         document_symbol_provider: Some(OneOf::Left(true)),
         workspace_symbol_provider: Some(OneOf::Left(true)),
+        workspace: Some(WorkspaceServerCapabilities {
+            file_operations: Some(WorkspaceFileOperationsServerCapabilities {
+                will_rename: Some(FileOperationRegistrationOptions {
+                    filters: vec![FileOperationFilter {
+                        scheme: Some("file".to_string()),
+                        pattern: FileOperationPattern {
+                            glob: "**/*.{py,pyi}".to_string(),
+                            matches: Some(FileOperationPatternKind::File),
+                            options: None,
+                        },
+                    }],
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
         notebook_document_sync: Some(OneOf::Left(lsp_types::NotebookDocumentSyncOptions {
             save: Some(false),
             notebook_selector: [NotebookSelector::ByCells {
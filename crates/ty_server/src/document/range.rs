@@ -7,6 +7,7 @@ use ruff_db::source::{line_index, source_text};
 use ruff_source_file::LineIndex;
 use ruff_source_file::{OneIndexed, SourceLocation};
 use ruff_text_size::{Ranged, TextRange, TextSize};
+use rustc_hash::FxHashMap;
 
 /// A range in an LSP text document (cell or a regular document).
 #[derive(Clone, Debug, Default)]
@@ -220,6 +221,22 @@ pub(crate) trait ToRangeExt {
     /// * If range is out of bounds.
     fn to_lsp_range(&self, db: &dyn Db, file: File, encoding: PositionEncoding)
     -> Option<LspRange>;
+
+    /// Like [`Self::to_lsp_range`], but resolves the file's URI through `url_cache` instead of
+    /// recomputing it on every call.
+    ///
+    /// Turning a [`File`] into a [`Url`] isn't free (it's a path-to-URL conversion with
+    /// percent-encoding), and callers that convert many ranges from the same handful of files in
+    /// one go - a workspace-wide symbol search can return thousands of hits concentrated in a
+    /// few large modules - end up paying that cost once per range instead of once per file.
+    /// `url_cache` lets them pay it once per file instead.
+    fn to_lsp_range_with_cache(
+        &self,
+        db: &dyn Db,
+        file: File,
+        encoding: PositionEncoding,
+        url_cache: &mut FxHashMap<File, Option<lsp_types::Url>>,
+    ) -> Option<LspRange>;
 }
 
 fn u32_index_to_usize(index: u32) -> usize {
@@ -287,6 +304,28 @@ impl ToRangeExt for TextRange {
         db: &dyn Db,
         file: File,
         encoding: PositionEncoding,
+    ) -> Option<LspRange> {
+        self.to_lsp_range_impl(db, file, encoding, None)
+    }
+
+    fn to_lsp_range_with_cache(
+        &self,
+        db: &dyn Db,
+        file: File,
+        encoding: PositionEncoding,
+        url_cache: &mut FxHashMap<File, Option<lsp_types::Url>>,
+    ) -> Option<LspRange> {
+        self.to_lsp_range_impl(db, file, encoding, Some(url_cache))
+    }
+}
+
+impl TextRange {
+    fn to_lsp_range_impl(
+        &self,
+        db: &dyn Db,
+        file: File,
+        encoding: PositionEncoding,
+        url_cache: Option<&mut FxHashMap<File, Option<lsp_types::Url>>>,
     ) -> Option<LspRange> {
         let source = source_text(db, file);
         let index = line_index(db, file);
@@ -320,7 +359,10 @@ impl ToRangeExt for TextRange {
 
         let range = text_range_to_lsp_range(*self, &source, &index, encoding);
 
-        let uri = file_to_url(db, file);
+        let uri = match url_cache {
+            Some(cache) => cache.entry(file).or_insert_with(|| file_to_url(db, file)).clone(),
+            None => file_to_url(db, file),
+        };
         Some(LspRange { range, uri })
     }
 }
@@ -0,0 +1,99 @@
+//! A direct, in-process Rust API for querying types, for embedders that link against
+//! `ty_server` instead of speaking JSON-RPC over a pipe.
+//!
+//! This answers the same question `textDocument/hover` does, but as a plain function call
+//! against a project database held in this process - no [`Server`](crate::Server), no
+//! connection, and no serialization in the loop. See [`crate::tsp`] for the wire-level
+//! `typeServer/*` extension this is not: that module is for out-of-process clients that do
+//! want JSON-RPC; this one is for callers that specifically want to avoid it.
+
+use ruff_db::files::system_path_to_file;
+use ruff_db::source::{line_index, source_text};
+use ruff_db::system::{OsSystem, SystemPath};
+use ruff_source_file::{OneIndexed, PositionEncoding, SourceLocation};
+use ty_ide::MarkupKind;
+use ty_project::{ProjectDatabase, ProjectMetadata};
+
+/// A handle onto an in-process project database, for answering type queries without a
+/// language-server session.
+///
+/// Construct one with [`TypeServerHandle::open`] and reuse it across calls to
+/// [`get_type`](TypeServerHandle::get_type) - opening a project discovers its configuration
+/// and builds a salsa database, which isn't free, while the database itself incrementally
+/// recomputes only what changed between queries.
+pub struct TypeServerHandle {
+    db: ProjectDatabase,
+}
+
+impl TypeServerHandle {
+    /// Opens the project rooted at `project_path`, discovering and applying its configuration
+    /// the same way `ty check` does.
+    pub fn open(project_path: &SystemPath) -> anyhow::Result<Self> {
+        let system = OsSystem::new(project_path);
+        let mut project_metadata = ProjectMetadata::discover(project_path, &system)?;
+        project_metadata.apply_configuration_files(&system)?;
+
+        Ok(Self {
+            db: ProjectDatabase::new(project_metadata, system)?,
+        })
+    }
+
+    /// Returns the rendered type and documentation (if any) at the one-indexed `line`/`column`
+    /// in `path`, along with the source range it applies to, or `None` if there's nothing to
+    /// show there.
+    ///
+    /// `path` must be a file within this handle's project; `column` is a UTF-32 code point
+    /// offset, matching [`PositionEncoding::Utf32`].
+    pub fn get_type(
+        &self,
+        path: &SystemPath,
+        line: OneIndexed,
+        column: OneIndexed,
+    ) -> anyhow::Result<Option<TypeAtPosition>> {
+        let file = system_path_to_file(&self.db, path)
+            .map_err(|error| anyhow::anyhow!("failed to open `{path}`: {error}"))?;
+
+        let text = source_text(&self.db, file);
+        let index = line_index(&self.db, file);
+        let offset = index.offset(
+            SourceLocation {
+                line,
+                character_offset: column,
+            },
+            text.as_str(),
+            PositionEncoding::Utf32,
+        );
+
+        let Some(range_info) = ty_ide::hover(&self.db, file, offset) else {
+            return Ok(None);
+        };
+
+        let contents = range_info
+            .display(&self.db, MarkupKind::PlainText)
+            .to_string();
+        let start = index.source_location(
+            range_info.file_range().range().start(),
+            text.as_str(),
+            PositionEncoding::Utf32,
+        );
+        let end = index.source_location(
+            range_info.file_range().range().end(),
+            text.as_str(),
+            PositionEncoding::Utf32,
+        );
+
+        Ok(Some(TypeAtPosition {
+            contents,
+            start,
+            end,
+        }))
+    }
+}
+
+/// The result of [`TypeServerHandle::get_type`]: a rendered type/documentation string and the
+/// source range it was computed for.
+pub struct TypeAtPosition {
+    pub contents: String,
+    pub start: SourceLocation,
+    pub end: SourceLocation,
+}
@@ -5,8 +5,15 @@ use lsp_server::Connection;
 use ruff_db::system::{OsSystem, SystemPathBuf};
 
 use crate::db::Db;
+pub use crate::embed::TypeServerHandle;
 pub use crate::logging::{LogLevel, init_logging};
-pub use crate::server::{PartialWorkspaceProgress, PartialWorkspaceProgressParams, Server};
+pub use crate::server::middleware;
+pub use crate::server::{
+    PartialWorkspaceProgress, PartialWorkspaceProgressParams, PartialWorkspaceSymbolProgress,
+    PartialWorkspaceSymbolProgressParams, SearchSymbols, SearchSymbolsParams, SearchSymbolsResult,
+    Server, ServerBuilder, ServerHandle, VisibleRanges, VisibleRangesParams,
+};
+pub use crate::session::tsp;
 pub use crate::session::{ClientOptions, DiagnosticMode};
 pub use document::{NotebookDocument, PositionEncoding, TextDocument};
 pub(crate) use session::Session;
@@ -14,6 +21,7 @@ pub(crate) use session::Session;
 mod capabilities;
 mod db;
 mod document;
+mod embed;
 mod logging;
 mod server;
 mod session;
@@ -15,18 +15,38 @@ use std::sync::Arc;
 mod api;
 mod lazy_work_done_progress;
 mod main_loop;
+pub mod middleware;
 mod schedule;
 
 use crate::session::client::Client;
 pub(crate) use api::Error;
-pub(crate) use api::publish_settings_diagnostics;
+pub(crate) use api::{
+    publish_diagnostics, publish_diagnostics_if_needed, publish_settings_diagnostics,
+};
 pub(crate) use main_loop::{
     Action, ConnectionSender, Event, MainLoopReceiver, MainLoopSender, SendRequest,
 };
 pub(crate) type Result<T> = std::result::Result<T, api::Error>;
-pub use api::{PartialWorkspaceProgress, PartialWorkspaceProgressParams};
+pub use api::{
+    PartialWorkspaceProgress, PartialWorkspaceProgressParams, PartialWorkspaceSymbolProgress,
+    PartialWorkspaceSymbolProgressParams, SearchSymbols, SearchSymbolsParams, SearchSymbolsResult,
+    VisibleRanges, VisibleRangesParams,
+};
 
 pub struct Server {
+    /// The JSON-RPC transport.
+    ///
+    /// This intentionally does not accept batched JSON-RPC arrays (multiple requests framed as
+    /// one message, dispatched as a unit). Framing and parsing happens entirely inside
+    /// `lsp_server`'s own I/O thread, which hands us one already-parsed [`Message`] at a time,
+    /// and the LSP spec itself doesn't support batching for the same reason we wouldn't want to
+    /// build it on top: ordering across a single connection has to stay well-defined, and a
+    /// batch blurs that. A client that wants to cut framing overhead for bulk TSP queries should
+    /// send fewer, larger requests instead (e.g. a single `typeServer/*` call covering a batch of
+    /// positions) - that's a real solution to the same problem, whereas transport-level batching
+    /// isn't something this server is taking on.
+    ///
+    /// [`Message`]: lsp_server::Message
     connection: Connection,
     worker_threads: NonZeroUsize,
     main_loop_receiver: MainLoopReceiver,
@@ -34,6 +54,79 @@ pub struct Server {
     session: Session,
 }
 
+/// A builder for [`Server`], for embedders that want to customize construction options
+/// without reaching for [`Server::new`]'s positional arguments directly.
+///
+/// Construct one with [`ServerBuilder::new`], chain any `with_`-style setters, then call
+/// [`ServerBuilder::build`] to perform the LSP initialization handshake and get back a
+/// [`Server`] ready to [`run`](Server::run).
+pub struct ServerBuilder {
+    connection: Connection,
+    native_system: Arc<dyn System + 'static + Send + Sync + RefUnwindSafe>,
+    worker_threads: NonZeroUsize,
+    in_test: bool,
+    middleware: Vec<Box<dyn middleware::TspMiddleware>>,
+}
+
+impl ServerBuilder {
+    /// Creates a new builder with the default worker thread count (the number of available
+    /// CPUs, capped at 4) and `in_test` disabled.
+    pub fn new(
+        connection: Connection,
+        native_system: Arc<dyn System + 'static + Send + Sync + RefUnwindSafe>,
+    ) -> Self {
+        let default_worker_threads = std::thread::available_parallelism()
+            .unwrap_or_else(|_| NonZeroUsize::new(4).unwrap())
+            .min(NonZeroUsize::new(4).unwrap());
+
+        Self {
+            connection,
+            native_system,
+            worker_threads: default_worker_threads,
+            in_test: false,
+            middleware: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn worker_threads(mut self, worker_threads: NonZeroUsize) -> Self {
+        self.worker_threads = worker_threads;
+        self
+    }
+
+    /// Marks this server as running under test, which disables logging initialization that
+    /// would otherwise conflict with the test harness's own tracing setup.
+    #[must_use]
+    pub fn in_test(mut self, in_test: bool) -> Self {
+        self.in_test = in_test;
+        self
+    }
+
+    /// Registers a [`middleware::TspMiddleware`] hook to run around every request. Hooks run
+    /// in registration order; call this repeatedly to register more than one.
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: Box<dyn middleware::TspMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Performs the LSP initialization handshake and builds the [`Server`].
+    pub fn build(self) -> crate::Result<Server> {
+        let mut server = Server::new(
+            self.worker_threads,
+            self.connection,
+            self.native_system,
+            self.in_test,
+        )?;
+
+        for middleware in self.middleware {
+            server.session.register_middleware(middleware);
+        }
+
+        Ok(server)
+    }
+}
+
 impl Server {
     pub fn new(
         worker_threads: NonZeroUsize,
@@ -129,21 +222,6 @@ impl Server {
                 )
             })?;
 
-        let workspace_urls = if workspace_urls.len() > 1 {
-            let first_workspace = workspace_urls.into_iter().next().unwrap();
-            tracing::warn!(
-                "Multiple workspaces are not yet supported, using the first workspace: {}",
-                &first_workspace
-            );
-            client.show_warning_message(format_args!(
-                "Multiple workspaces are not yet supported, using the first workspace: {}",
-                &first_workspace,
-            ));
-            vec![first_workspace]
-        } else {
-            workspace_urls
-        };
-
         Ok(Self {
             connection,
             worker_threads,
@@ -160,6 +238,14 @@ impl Server {
         })
     }
 
+    /// Returns a cheap, cloneable [`ServerHandle`] for interacting with this server after
+    /// [`run`](Server::run) takes ownership of it and starts blocking.
+    pub fn handle(&self) -> ServerHandle {
+        ServerHandle {
+            main_loop_sender: self.main_loop_sender.clone(),
+        }
+    }
+
     pub fn run(mut self) -> crate::Result<()> {
         let client = Client::new(
             self.main_loop_sender.clone(),
@@ -186,6 +272,34 @@ impl Server {
     }
 }
 
+/// A cheap, cloneable handle to a running [`Server`].
+///
+/// [`Server::run`] takes `self` by value and blocks until the server shuts down, so there's no
+/// way to keep interacting with the `Server` itself once it's running. Embedders that want to
+/// reach into a running server - for example to adjust its worker pool size in response to
+/// machine load - should grab a handle with [`Server::handle`] beforehand and hold on to that
+/// instead.
+#[derive(Clone)]
+pub struct ServerHandle {
+    main_loop_sender: MainLoopSender,
+}
+
+impl ServerHandle {
+    /// Grows the background worker pool to `threads` worker threads, so the embedder can adapt
+    /// to machine load without restarting the server (and losing all of its session state) to
+    /// change [`ServerBuilder::worker_threads`].
+    ///
+    /// This can only grow the pool, not shrink it; see `thread::Pool::set_size` for why.
+    pub fn set_background_worker_threads(&self, threads: NonZeroUsize) {
+        // The receiver is dropped once the main loop exits. A handle can outlive the server
+        // it came from, so unlike most `main_loop_sender` sends in this crate, a disconnected
+        // channel here is an expected outcome, not a bug, and is silently ignored.
+        self.main_loop_sender
+            .send(Event::Action(Action::SetBackgroundWorkerThreads(threads)))
+            .ok();
+    }
+}
+
 type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + 'static + Sync + Send>;
 
 struct ServerPanicHookHandler {
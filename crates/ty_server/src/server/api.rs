@@ -17,8 +17,14 @@ mod traits;
 use self::traits::{NotificationHandler, RequestHandler};
 use super::{Result, schedule::BackgroundSchedule};
 use crate::session::client::Client;
-pub(crate) use diagnostics::publish_settings_diagnostics;
-pub use requests::{PartialWorkspaceProgress, PartialWorkspaceProgressParams};
+pub(crate) use diagnostics::{
+    publish_diagnostics, publish_diagnostics_if_needed, publish_settings_diagnostics, warm_document,
+};
+pub use notifications::{VisibleRanges, VisibleRangesParams};
+pub use requests::{
+    PartialWorkspaceProgress, PartialWorkspaceProgressParams, PartialWorkspaceSymbolProgress,
+    PartialWorkspaceSymbolProgressParams, SearchSymbols, SearchSymbolsParams, SearchSymbolsResult,
+};
 use ruff_db::panic::PanicError;
 
 /// Processes a request from the client to the server.
@@ -35,6 +41,14 @@ pub(super) fn request(req: server::Request) -> Task {
         requests::CodeActionRequestHandler::METHOD => background_document_request_task::<
             requests::CodeActionRequestHandler,
         >(req, BackgroundSchedule::Worker),
+        requests::CodeLensRequestHandler::METHOD => background_document_request_task::<
+            requests::CodeLensRequestHandler,
+        >(req, BackgroundSchedule::Worker),
+        requests::CodeLensResolveRequestHandler::METHOD => background_document_request_task::<
+            requests::CodeLensResolveRequestHandler,
+        >(
+            req, BackgroundSchedule::Worker
+        ),
         requests::DocumentDiagnosticRequestHandler::METHOD => background_document_request_task::<
             requests::DocumentDiagnosticRequestHandler,
         >(
@@ -45,6 +59,9 @@ pub(super) fn request(req: server::Request) -> Task {
         >(
             req, BackgroundSchedule::Worker
         ),
+        requests::WillRenameFilesRequestHandler::METHOD => background_request_task::<
+            requests::WillRenameFilesRequestHandler,
+        >(req, BackgroundSchedule::Worker),
         requests::GotoTypeDefinitionRequestHandler::METHOD => background_document_request_task::<
             requests::GotoTypeDefinitionRequestHandler,
         >(
@@ -69,12 +86,26 @@ pub(super) fn request(req: server::Request) -> Task {
         >(
             req, BackgroundSchedule::Worker
         ),
+        requests::DocumentLinkRequestHandler::METHOD => background_document_request_task::<
+            requests::DocumentLinkRequestHandler,
+        >(req, BackgroundSchedule::Worker),
         requests::InlayHintRequestHandler::METHOD => background_document_request_task::<
             requests::InlayHintRequestHandler,
         >(req, BackgroundSchedule::Worker),
+        requests::LinkedEditingRangeRequestHandler::METHOD => background_document_request_task::<
+            requests::LinkedEditingRangeRequestHandler,
+        >(req, BackgroundSchedule::Worker),
+        requests::MonikerRequestHandler::METHOD => background_document_request_task::<
+            requests::MonikerRequestHandler,
+        >(req, BackgroundSchedule::Worker),
         requests::SemanticTokensRequestHandler::METHOD => background_document_request_task::<
             requests::SemanticTokensRequestHandler,
         >(req, BackgroundSchedule::Worker),
+        requests::SemanticTokensDeltaRequestHandler::METHOD => background_document_request_task::<
+            requests::SemanticTokensDeltaRequestHandler,
+        >(
+            req, BackgroundSchedule::Worker
+        ),
         requests::SemanticTokensRangeRequestHandler::METHOD => background_document_request_task::<
             requests::SemanticTokensRangeRequestHandler,
         >(
@@ -94,9 +125,44 @@ pub(super) fn request(req: server::Request) -> Task {
         >(
             req, BackgroundSchedule::LatencySensitive
         ),
+        requests::CompletionResolveRequestHandler::METHOD => background_document_request_task::<
+            requests::CompletionResolveRequestHandler,
+        >(
+            req, BackgroundSchedule::LatencySensitive
+        ),
         requests::SelectionRangeRequestHandler::METHOD => background_document_request_task::<
             requests::SelectionRangeRequestHandler,
         >(req, BackgroundSchedule::Worker),
+        requests::PrepareCallHierarchyRequestHandler::METHOD => background_document_request_task::<
+            requests::PrepareCallHierarchyRequestHandler,
+        >(req, BackgroundSchedule::Worker),
+        requests::CallHierarchyIncomingCallsRequestHandler::METHOD => {
+            background_document_request_task::<requests::CallHierarchyIncomingCallsRequestHandler>(
+                req,
+                BackgroundSchedule::Worker,
+            )
+        }
+        requests::CallHierarchyOutgoingCallsRequestHandler::METHOD => {
+            background_document_request_task::<requests::CallHierarchyOutgoingCallsRequestHandler>(
+                req,
+                BackgroundSchedule::Worker,
+            )
+        }
+        requests::PrepareTypeHierarchyRequestHandler::METHOD => background_document_request_task::<
+            requests::PrepareTypeHierarchyRequestHandler,
+        >(req, BackgroundSchedule::Worker),
+        requests::TypeHierarchySupertypesRequestHandler::METHOD => {
+            background_document_request_task::<requests::TypeHierarchySupertypesRequestHandler>(
+                req,
+                BackgroundSchedule::Worker,
+            )
+        }
+        requests::TypeHierarchySubtypesRequestHandler::METHOD => {
+            background_document_request_task::<requests::TypeHierarchySubtypesRequestHandler>(
+                req,
+                BackgroundSchedule::Worker,
+            )
+        }
         requests::DocumentSymbolRequestHandler::METHOD => background_document_request_task::<
             requests::DocumentSymbolRequestHandler,
         >(req, BackgroundSchedule::Worker),
@@ -105,6 +171,11 @@ pub(super) fn request(req: server::Request) -> Task {
         >(
             req, BackgroundSchedule::Worker
         ),
+        requests::SearchSymbolsRequestHandler::METHOD => background_request_task::<
+            requests::SearchSymbolsRequestHandler,
+        >(
+            req, BackgroundSchedule::Tsp
+        ),
         lsp_types::request::Shutdown::METHOD => sync_request_task::<requests::ShutdownHandler>(req),
 
         method => {
@@ -160,9 +231,15 @@ pub(super) fn notification(notif: server::Notification) -> Task {
         notifications::DidChangeWatchedFiles::METHOD => {
             sync_notification_task::<notifications::DidChangeWatchedFiles>(notif)
         }
+        notifications::DidChangeConfigurationHandler::METHOD => {
+            sync_notification_task::<notifications::DidChangeConfigurationHandler>(notif)
+        }
         lsp_types::notification::Cancel::METHOD => {
             sync_notification_task::<notifications::CancelNotificationHandler>(notif)
         }
+        notifications::VisibleRanges::METHOD => {
+            sync_notification_task::<notifications::VisibleRangesHandler>(notif)
+        }
         lsp_types::notification::SetTrace::METHOD => {
             tracing::trace!("Ignoring `setTrace` notification");
             return Task::nothing();
@@ -185,6 +262,13 @@ pub(super) fn notification(notif: server::Notification) -> Task {
     })
 }
 
+// Each request task logs the wall time spent inside its handler at debug level under the
+// `request` span, so a slow handler shows up in user-provided logs without needing a
+// reproduction. We don't also report CPU time or salsa query counts here: process-wide CPU
+// time needs a platform-specific syscall (e.g. `getrusage`) that isn't wired up anywhere in
+// this tree today, and salsa query counting currently only exists on `TestDb` (see
+// `take_salsa_events` in `ty_project::db`) rather than on the database used by a running
+// server. Either would be a reasonable follow-up but is a bigger change than adding a timer.
 fn sync_request_task<R: traits::SyncRequestHandler>(req: server::Request) -> Result<Task>
 where
     <<R as RequestHandler>::RequestType as Request>::Params: UnwindSafe,
@@ -192,7 +276,9 @@ where
     let (id, params) = cast_request::<R>(req)?;
     Ok(Task::sync(move |session, client: &Client| {
         let _span = tracing::debug_span!("request", %id, method = R::METHOD).entered();
+        let start = std::time::Instant::now();
         let result = R::run(session, client, params);
+        tracing::debug!(wall_time = ?start.elapsed(), "Request {} finished", R::METHOD);
         respond::<R>(&id, result, client);
     }))
 }
@@ -234,10 +320,14 @@ where
                 return;
             }
 
-            if let Err(error) = ruff_db::panic::catch_unwind(|| {
+            let start = std::time::Instant::now();
+            let result = ruff_db::panic::catch_unwind(|| {
                 let snapshot = snapshot;
-                R::handle_request(&id, snapshot.0, client, params);
-            }) {
+                R::handle_request(&id, snapshot.0, client, params, &cancellation_token);
+            });
+            tracing::debug!(wall_time = ?start.elapsed(), "Request {} finished", R::METHOD);
+
+            if let Err(error) = result {
                 panic_response::<R>(&id, client, &error, retry);
             }
         })
@@ -301,11 +391,15 @@ where
                 return;
             }
 
-            if let Err(error) = ruff_db::panic::catch_unwind(|| {
+            let start = std::time::Instant::now();
+            let result = ruff_db::panic::catch_unwind(|| {
                 salsa::attach(&db, || {
                     R::handle_request(&id, &db, document, client, params);
                 });
-            }) {
+            });
+            tracing::debug!(wall_time = ?start.elapsed(), "Request {} finished", R::METHOD);
+
+            if let Err(error) = result {
                 panic_response::<R>(&id, client, &error, retry);
             }
         })
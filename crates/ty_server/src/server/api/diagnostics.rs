@@ -158,7 +158,7 @@ pub(super) fn clear_diagnostics(uri: &lsp_types::Url, client: &Client) {
 /// does not support pull diagnostics for notebooks or cells (as of 2025-11-12).
 ///
 /// [publish diagnostics notification]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#textDocument_publishDiagnostics
-pub(super) fn publish_diagnostics_if_needed(
+pub(crate) fn publish_diagnostics_if_needed(
     document: &DocumentHandle,
     session: &Session,
     client: &Client,
@@ -173,7 +173,7 @@ pub(super) fn publish_diagnostics_if_needed(
 
 /// Publishes the diagnostics for the given document snapshot using the [publish diagnostics
 /// notification].
-pub(super) fn publish_diagnostics(document: &DocumentHandle, session: &Session, client: &Client) {
+pub(crate) fn publish_diagnostics(document: &DocumentHandle, session: &Session, client: &Client) {
     let db = session.project_db(document.notebook_or_file_path());
 
     let Some(diagnostics) = compute_diagnostics(db, document, session.position_encoding()) else {
@@ -201,6 +201,46 @@ pub(super) fn publish_diagnostics(document: &DocumentHandle, session: &Session,
     }
 }
 
+/// Publishes `document`'s diagnostics immediately, or, if `diagnosticsDebounceMs` is configured,
+/// schedules them to be published once that many milliseconds pass without another edit to the
+/// document.
+///
+/// This exists so that a burst of `didChange` notifications from fast typing collapses into a
+/// single recompute once the user pauses, instead of recomputing (and republishing) diagnostics
+/// for every intermediate keystroke.
+pub(super) fn publish_diagnostics_debounced(
+    document: &DocumentHandle,
+    session: &mut Session,
+    client: &Client,
+) {
+    match session
+        .initialization_options()
+        .diagnostics_debounce_ms
+        .filter(|&ms| ms > 0)
+    {
+        Some(debounce_ms) => session.schedule_diagnostics_publish(
+            document.url().clone(),
+            std::time::Duration::from_millis(debounce_ms),
+        ),
+        None => publish_diagnostics(document, session, client),
+    }
+}
+
+/// Like [`publish_diagnostics_if_needed`], but debounces rapid-fire edits according to the
+/// `diagnosticsDebounceMs` initialization option. See [`publish_diagnostics_debounced`].
+pub(super) fn publish_diagnostics_if_needed_debounced(
+    document: &DocumentHandle,
+    session: &mut Session,
+    client: &Client,
+) {
+    if !document.is_cell_or_notebook() && session.client_capabilities().supports_pull_diagnostics()
+    {
+        return;
+    }
+
+    publish_diagnostics_debounced(document, session, client);
+}
+
 /// Publishes settings diagnostics for all the project at the given path
 /// using the [publish diagnostics notification].
 ///
@@ -275,6 +315,21 @@ pub(crate) fn publish_settings_diagnostics(
     }
 }
 
+/// Forces the same inference that [`compute_diagnostics`] performs, without building or
+/// publishing the resulting diagnostics.
+///
+/// Used to opportunistically warm the Salsa caches (types of the document's own definitions and,
+/// transitively, of whatever it imports) for documents that were just opened but whose
+/// diagnostics aren't computed eagerly, so that the *first* real request for the document (a
+/// pull-diagnostic, hover, or completion) isn't the one that pays for cold inference.
+pub(super) fn warm_document(db: &ProjectDatabase, document: &DocumentHandle) {
+    let Some(file) = document.notebook_or_file(db) else {
+        return;
+    };
+
+    db.check_file(file);
+}
+
 pub(super) fn compute_diagnostics(
     db: &ProjectDatabase,
     document: &DocumentHandle,
@@ -1,17 +1,23 @@
 mod cancel;
 mod did_change;
+mod did_change_configuration;
 mod did_change_notebook;
 mod did_change_watched_files;
 mod did_close;
 mod did_close_notebook;
 mod did_open;
 mod did_open_notebook;
+mod visible_ranges;
 
 pub(super) use cancel::CancelNotificationHandler;
 pub(super) use did_change::DidChangeTextDocumentHandler;
+pub(super) use did_change_configuration::DidChangeConfigurationHandler;
 pub(super) use did_change_notebook::DidChangeNotebookHandler;
 pub(super) use did_change_watched_files::DidChangeWatchedFiles;
 pub(super) use did_close::DidCloseTextDocumentHandler;
 pub(super) use did_close_notebook::DidCloseNotebookHandler;
 pub(super) use did_open::DidOpenTextDocumentHandler;
 pub(super) use did_open_notebook::DidOpenNotebookHandler;
+pub(super) use visible_ranges::VisibleRangesHandler;
+
+pub use visible_ranges::{VisibleRanges, VisibleRangesParams};
@@ -4,7 +4,7 @@ use lsp_types::{DidChangeTextDocumentParams, VersionedTextDocumentIdentifier};
 
 use crate::server::Result;
 use crate::server::api::LSPResult;
-use crate::server::api::diagnostics::publish_diagnostics_if_needed;
+use crate::server::api::diagnostics::publish_diagnostics_if_needed_debounced;
 use crate::server::api::traits::{NotificationHandler, SyncNotificationHandler};
 use crate::session::Session;
 use crate::session::client::Client;
@@ -34,7 +34,7 @@ impl SyncNotificationHandler for DidChangeTextDocumentHandler {
             .update_text_document(session, content_changes, version)
             .with_failure_code(ErrorCode::InternalError)?;
 
-        publish_diagnostics_if_needed(&document, session, client);
+        publish_diagnostics_if_needed_debounced(&document, session, client);
 
         Ok(())
     }
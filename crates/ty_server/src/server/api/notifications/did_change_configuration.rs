@@ -0,0 +1,88 @@
+use lsp_types::notification::DidChangeConfiguration;
+use lsp_types::{ConfigurationItem, ConfigurationParams, DidChangeConfigurationParams};
+use serde_json::Value;
+
+use crate::server::api::traits::{NotificationHandler, SyncNotificationHandler};
+use crate::server::{Action, Result};
+use crate::session::client::Client;
+use crate::session::{ClientOptions, Session};
+
+pub(crate) struct DidChangeConfigurationHandler;
+
+impl NotificationHandler for DidChangeConfigurationHandler {
+    type NotificationType = DidChangeConfiguration;
+}
+
+impl SyncNotificationHandler for DidChangeConfigurationHandler {
+    fn run(
+        session: &mut Session,
+        client: &Client,
+        _params: DidChangeConfigurationParams,
+    ) -> Result<()> {
+        // We only support the pull-based configuration model (`workspace/configuration`); clients
+        // that don't support it have no way to tell us which workspace their `settings` apply to,
+        // so there's nothing we can reload.
+        if !session.client_capabilities().supports_workspace_configuration() {
+            tracing::debug!(
+                "Ignoring `workspace/didChangeConfiguration` because the client doesn't support \
+                 workspace configuration pull"
+            );
+            return Ok(());
+        }
+
+        let urls: Vec<_> = session.workspaces().urls().cloned().collect();
+
+        let items = urls
+            .iter()
+            .map(|root| ConfigurationItem {
+                scope_uri: Some(root.clone()),
+                section: Some("ty".to_string()),
+            })
+            .collect();
+
+        tracing::debug!("Requesting workspace configuration to reload settings");
+        client.send_request::<lsp_types::request::WorkspaceConfiguration>(
+            session,
+            ConfigurationParams { items },
+            |client, result: Vec<Value>| {
+                tracing::debug!("Received workspace configurations, reloading workspaces");
+
+                // This shouldn't fail because, as per the spec, the client needs to provide a
+                // `null` value even if it cannot provide a configuration for a workspace.
+                assert_eq!(
+                    result.len(),
+                    urls.len(),
+                    "Mismatch in number of workspace URLs ({}) and configuration results ({})",
+                    urls.len(),
+                    result.len()
+                );
+
+                let workspaces_with_options: Vec<_> = urls
+                    .into_iter()
+                    .zip(result)
+                    .map(|(url, value)| {
+                        if value.is_null() {
+                            tracing::debug!(
+                                "No workspace options provided for {url}, keeping the current options"
+                            );
+                            return (url, ClientOptions::default());
+                        }
+                        let options: ClientOptions =
+                            serde_json::from_value(value).unwrap_or_else(|err| {
+                                tracing::error!(
+                                    "Failed to deserialize workspace options for {url}: {err}. \
+                                     Using default options"
+                                );
+                                ClientOptions::default()
+                            });
+                        (url, options)
+                    })
+                    .collect();
+
+                client.queue_action(Action::ReloadWorkspaces(workspaces_with_options));
+            },
+        );
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use lsp_types::notification as notif;
 
 use crate::server::Result;
 use crate::server::api::LSPResult;
-use crate::server::api::diagnostics::publish_diagnostics;
+use crate::server::api::diagnostics::publish_diagnostics_debounced;
 use crate::server::api::traits::{NotificationHandler, SyncNotificationHandler};
 use crate::session::Session;
 use crate::session::client::Client;
@@ -33,7 +33,7 @@ impl SyncNotificationHandler for DidChangeNotebookHandler {
             .with_failure_code(ErrorCode::InternalError)?;
 
         // Always publish diagnostics because notebooks only support publish diagnostics.
-        publish_diagnostics(&document, session, client);
+        publish_diagnostics_debounced(&document, session, client);
 
         Ok(())
     }
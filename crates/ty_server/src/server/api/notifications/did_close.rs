@@ -37,6 +37,10 @@ impl SyncNotificationHandler for DidCloseTextDocumentHandler {
             clear_diagnostics_if_needed(&document, session, client);
         }
 
+        // Drop any visibility hint recorded for this document, so a closed document never lingers
+        // at the front of `take_due_diagnostics_publishes`'s priority order.
+        session.set_visible_ranges(uri, Vec::new());
+
         Ok(())
     }
 }
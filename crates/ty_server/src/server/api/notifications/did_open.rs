@@ -2,9 +2,9 @@ use lsp_types::notification::DidOpenTextDocument;
 use lsp_types::{DidOpenTextDocumentParams, TextDocumentItem};
 
 use crate::TextDocument;
-use crate::server::Result;
 use crate::server::api::diagnostics::publish_diagnostics_if_needed;
 use crate::server::api::traits::{NotificationHandler, SyncNotificationHandler};
+use crate::server::{Action, Result};
 use crate::session::Session;
 use crate::session::client::Client;
 
@@ -36,6 +36,15 @@ impl SyncNotificationHandler for DidOpenTextDocumentHandler {
 
         publish_diagnostics_if_needed(&document, session, client);
 
+        // Pull-diagnostics clients don't get their document checked eagerly above (they'll pull
+        // it themselves), so there's otherwise no inference done for this document until the
+        // client's first real request. Use idle worker time to do it ahead of that request.
+        if !document.is_cell_or_notebook()
+            && session.client_capabilities().supports_pull_diagnostics()
+        {
+            client.queue_action(Action::WarmDocument(document.url().clone()));
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,56 @@
+use lsp_types::notification::Notification;
+use lsp_types::{Range, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::server::Result;
+use crate::server::api::traits::{NotificationHandler, SyncNotificationHandler};
+use crate::session::Session;
+use crate::session::client::Client;
+
+/// A custom `typeServer/visibleRanges` notification.
+///
+/// Clients that speak the type-server protocol can send this whenever the set of visible lines
+/// in a document changes (scrolling, switching tabs) as a hint for which open documents the user
+/// is actually looking at right now. There's no dedicated prefetch or member-table machinery to
+/// feed in this tree, so the hint is used the same way visibility already matters elsewhere: it
+/// reprioritizes the existing debounced `publishDiagnostics` queue (see
+/// [`Session::take_due_diagnostics_publishes`]) so that visible documents are reported on before
+/// ones that are merely open in a background tab.
+pub enum VisibleRanges {}
+
+impl Notification for VisibleRanges {
+    type Params = VisibleRangesParams;
+    const METHOD: &'static str = "typeServer/visibleRanges";
+}
+
+// `ranges` is the "batched ranges" case worth checking against borrowed deserialization: it's
+// owned rather than `Cow<[Range]>` on purpose, because `SyncNotificationHandler::run` moves it
+// straight into `Session::set_visible_ranges`, which stores it in the session for as long as the
+// document stays open - there's no request-scoped buffer here to borrow from in the first place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VisibleRangesParams {
+    pub uri: Url,
+
+    /// The line ranges the client currently has visible for `uri`. Empty means the document has
+    /// scrolled out of view entirely (e.g. its tab was backgrounded) without being closed.
+    pub ranges: Vec<Range>,
+}
+
+pub(crate) struct VisibleRangesHandler;
+
+impl NotificationHandler for VisibleRangesHandler {
+    type NotificationType = VisibleRanges;
+}
+
+impl SyncNotificationHandler for VisibleRangesHandler {
+    fn run(session: &mut Session, _client: &Client, params: VisibleRangesParams) -> Result<()> {
+        if !session.initialization_options().tsp_enabled.unwrap_or(true) {
+            return Ok(());
+        }
+
+        session.set_visible_ranges(params.uri, params.ranges);
+
+        Ok(())
+    }
+}
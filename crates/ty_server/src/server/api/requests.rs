@@ -1,7 +1,13 @@
+mod call_hierarchy_incoming;
+mod call_hierarchy_outgoing;
 mod code_action;
+mod code_lens;
+mod code_lens_resolve;
 mod completion;
+mod completion_resolve;
 mod diagnostic;
 mod doc_highlights;
+mod document_link;
 mod document_symbols;
 mod execute_command;
 mod goto_declaration;
@@ -9,21 +15,36 @@ mod goto_definition;
 mod goto_type_definition;
 mod hover;
 mod inlay_hints;
+mod linked_editing_range;
+mod moniker;
+mod prepare_call_hierarchy;
 mod prepare_rename;
+mod prepare_type_hierarchy;
 mod references;
 mod rename;
+mod search_symbols;
 mod selection_range;
 mod semantic_tokens;
+mod semantic_tokens_delta;
 mod semantic_tokens_range;
 mod shutdown;
 mod signature_help;
+mod type_hierarchy_subtypes;
+mod type_hierarchy_supertypes;
+mod will_rename_files;
 mod workspace_diagnostic;
 mod workspace_symbols;
 
+pub(super) use call_hierarchy_incoming::CallHierarchyIncomingCallsRequestHandler;
+pub(super) use call_hierarchy_outgoing::CallHierarchyOutgoingCallsRequestHandler;
 pub(super) use code_action::CodeActionRequestHandler;
+pub(super) use code_lens::CodeLensRequestHandler;
+pub(super) use code_lens_resolve::CodeLensResolveRequestHandler;
 pub(super) use completion::CompletionRequestHandler;
+pub(super) use completion_resolve::CompletionResolveRequestHandler;
 pub(super) use diagnostic::DocumentDiagnosticRequestHandler;
 pub(super) use doc_highlights::DocumentHighlightRequestHandler;
+pub(super) use document_link::DocumentLinkRequestHandler;
 pub(super) use document_symbols::DocumentSymbolRequestHandler;
 pub(super) use execute_command::ExecuteCommand;
 pub(super) use goto_declaration::GotoDeclarationRequestHandler;
@@ -31,15 +52,26 @@ pub(super) use goto_definition::GotoDefinitionRequestHandler;
 pub(super) use goto_type_definition::GotoTypeDefinitionRequestHandler;
 pub(super) use hover::HoverRequestHandler;
 pub(super) use inlay_hints::InlayHintRequestHandler;
+pub(super) use linked_editing_range::LinkedEditingRangeRequestHandler;
+pub(super) use moniker::MonikerRequestHandler;
+pub(super) use prepare_call_hierarchy::PrepareCallHierarchyRequestHandler;
 pub(super) use prepare_rename::PrepareRenameRequestHandler;
+pub(super) use prepare_type_hierarchy::PrepareTypeHierarchyRequestHandler;
 pub(super) use references::ReferencesRequestHandler;
 pub(super) use rename::RenameRequestHandler;
+pub(super) use search_symbols::SearchSymbolsRequestHandler;
 pub(super) use selection_range::SelectionRangeRequestHandler;
 pub(super) use semantic_tokens::SemanticTokensRequestHandler;
+pub(super) use semantic_tokens_delta::SemanticTokensDeltaRequestHandler;
 pub(super) use semantic_tokens_range::SemanticTokensRangeRequestHandler;
 pub(super) use shutdown::ShutdownHandler;
 pub(super) use signature_help::SignatureHelpRequestHandler;
+pub(super) use type_hierarchy_subtypes::TypeHierarchySubtypesRequestHandler;
+pub(super) use type_hierarchy_supertypes::TypeHierarchySupertypesRequestHandler;
+pub(super) use will_rename_files::WillRenameFilesRequestHandler;
 pub(super) use workspace_diagnostic::WorkspaceDiagnosticRequestHandler;
 pub(super) use workspace_symbols::WorkspaceSymbolRequestHandler;
 
+pub use search_symbols::{SearchSymbols, SearchSymbolsParams, SearchSymbolsResult};
 pub use workspace_diagnostic::{PartialWorkspaceProgress, PartialWorkspaceProgressParams};
+pub use workspace_symbols::{PartialWorkspaceSymbolProgress, PartialWorkspaceSymbolProgressParams};
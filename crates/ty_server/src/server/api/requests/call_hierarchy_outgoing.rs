@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use lsp_types::request::CallHierarchyOutgoingCalls;
+use lsp_types::{CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, Url};
+use ty_ide::{outgoing_calls, prepare_call_hierarchy};
+use ty_project::ProjectDatabase;
+
+use crate::document::{PositionExt, ToRangeExt};
+use crate::server::api::requests::prepare_call_hierarchy::to_lsp_call_hierarchy_item;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct CallHierarchyOutgoingCallsRequestHandler;
+
+impl RequestHandler for CallHierarchyOutgoingCallsRequestHandler {
+    type RequestType = CallHierarchyOutgoingCalls;
+}
+
+impl BackgroundDocumentRequestHandler for CallHierarchyOutgoingCallsRequestHandler {
+    fn document_url(params: &CallHierarchyOutgoingCallsParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.item.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> crate::server::Result<Option<Vec<CallHierarchyOutgoingCall>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.item.selection_range.start.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(item) = prepare_call_hierarchy(db, file, offset).and_then(|mut items| {
+            if items.is_empty() {
+                None
+            } else {
+                Some(items.remove(0))
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let calls: Vec<_> = outgoing_calls(db, &item)
+            .into_iter()
+            .filter_map(|call| {
+                let to = to_lsp_call_hierarchy_item(db, &call.to, snapshot.encoding())?;
+                let from_ranges: Vec<_> = call
+                    .from_ranges
+                    .into_iter()
+                    .filter_map(|range| {
+                        Some(
+                            range
+                                .to_lsp_range(db, file, snapshot.encoding())?
+                                .local_range(),
+                        )
+                    })
+                    .collect();
+
+                Some(CallHierarchyOutgoingCall { to, from_ranges })
+            })
+            .collect();
+
+        Ok(Some(calls))
+    }
+}
+
+impl RetriableRequestHandler for CallHierarchyOutgoingCallsRequestHandler {}
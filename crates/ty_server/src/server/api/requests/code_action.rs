@@ -5,12 +5,12 @@ use lsp_types::{self as types, NumberOrString, TextEdit, Url, request as req};
 use ruff_db::files::File;
 use ruff_diagnostics::Edit;
 use ruff_text_size::Ranged;
-use ty_ide::code_actions;
+use ty_ide::{add_annotation_actions, code_actions};
 use ty_project::ProjectDatabase;
 use types::{CodeActionKind, CodeActionOrCommand};
 
 use crate::db::Db;
-use crate::document::{RangeExt, ToRangeExt};
+use crate::document::{PositionExt, RangeExt, ToRangeExt};
 use crate::server::Result;
 use crate::server::api::RequestHandler;
 use crate::server::api::diagnostics::DiagnosticData;
@@ -41,8 +41,30 @@ impl BackgroundDocumentRequestHandler for CodeActionRequestHandler {
         let Some(file) = snapshot.to_notebook_or_file(db) else {
             return Ok(None);
         };
+        let encoding = snapshot.encoding();
         let mut actions = Vec::new();
 
+        if let Some(offset) =
+            params.range.start.to_text_size(db, file, snapshot.url(), encoding)
+        {
+            for action in add_annotation_actions(db, file, offset) {
+                actions.push(CodeActionOrCommand::CodeAction(lsp_types::CodeAction {
+                    title: action.title,
+                    kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                    diagnostics: None,
+                    edit: Some(lsp_types::WorkspaceEdit {
+                        changes: to_lsp_edits(db, file, encoding, action.edits),
+                        document_changes: None,
+                        change_annotations: None,
+                    }),
+                    is_preferred: Some(action.preferred),
+                    command: None,
+                    disabled: None,
+                    data: None,
+                }));
+            }
+        }
+
         for mut diagnostic in diagnostics.into_iter().filter(|diagnostic| {
             diagnostic.source.as_deref() == Some(DIAGNOSTIC_NAME)
                 && range_intersect(&diagnostic.range, &params.range)
@@ -79,7 +101,6 @@ impl BackgroundDocumentRequestHandler for CodeActionRequestHandler {
             // For instance, suggesting imports requires finding symbols for the entire project,
             // which is dubious when you're in the middle of resolving symbols.
             let url = snapshot.url();
-            let encoding = snapshot.encoding();
             if let Some(NumberOrString::String(diagnostic_id)) = &diagnostic.code
                 && let Some(range) = diagnostic.range.to_text_range(db, file, url, encoding)
             {
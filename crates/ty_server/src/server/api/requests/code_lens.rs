@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use lsp_types::request::CodeLensRequest;
+use lsp_types::{CodeLens as LspCodeLens, CodeLensParams, Url};
+use serde::{Deserialize, Serialize};
+use ty_ide::code_lenses;
+use ty_project::ProjectDatabase;
+
+use crate::document::ToRangeExt;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+/// Identifies which [`ty_ide::CodeLens`] this [`LspCodeLens`] came from so that
+/// `codeLens/resolve` can recompute its (project-wide, and therefore not-free) reference or
+/// subclass count without having to thread that state through the client round-trip itself.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CodeLensResolveData {
+    pub(crate) uri: Url,
+    pub(crate) offset: u32,
+}
+
+pub(crate) struct CodeLensRequestHandler;
+
+impl RequestHandler for CodeLensRequestHandler {
+    type RequestType = CodeLensRequest;
+}
+
+impl BackgroundDocumentRequestHandler for CodeLensRequestHandler {
+    fn document_url(params: &CodeLensParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        _params: CodeLensParams,
+    ) -> crate::server::Result<Option<Vec<LspCodeLens>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let encoding = snapshot.encoding();
+
+        let items: Vec<_> = code_lenses(db, file)
+            .into_iter()
+            .filter_map(|lens| {
+                let range = lens
+                    .range
+                    .to_lsp_range(db, file, encoding)?
+                    .local_range();
+
+                let data = serde_json::to_value(CodeLensResolveData {
+                    uri: snapshot.url().clone(),
+                    offset: lens.range.start().into(),
+                })
+                .ok();
+
+                Some(LspCodeLens {
+                    range,
+                    command: None,
+                    data,
+                })
+            })
+            .collect();
+
+        Ok((!items.is_empty()).then_some(items))
+    }
+}
+
+impl RetriableRequestHandler for CodeLensRequestHandler {}
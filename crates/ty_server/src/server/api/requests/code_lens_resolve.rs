@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+
+use lsp_types::{CodeLens as LspCodeLens, Command, Url};
+use ruff_text_size::TextSize;
+use ty_ide::{code_lenses, resolve_code_lens};
+use ty_project::ProjectDatabase;
+
+use crate::server::api::requests::code_lens::CodeLensResolveData;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct CodeLensResolveRequestHandler;
+
+impl RequestHandler for CodeLensResolveRequestHandler {
+    type RequestType = lsp_types::request::CodeLensResolve;
+}
+
+impl BackgroundDocumentRequestHandler for CodeLensResolveRequestHandler {
+    fn document_url(params: &LspCodeLens) -> Cow<'_, Url> {
+        resolve_data(params)
+            .map(|data| Cow::Owned(data.uri))
+            .unwrap_or_else(|| Cow::Owned(invalid_url()))
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: LspCodeLens,
+    ) -> crate::server::Result<Option<LspCodeLens>> {
+        let mut lens = params;
+
+        let Some(data) = resolve_data(&lens) else {
+            return Ok(Some(lens));
+        };
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(Some(lens));
+        };
+
+        let offset = TextSize::from(data.offset);
+        let Some(target) = code_lenses(db, file)
+            .into_iter()
+            .find(|candidate| candidate.range.start() == offset)
+        else {
+            return Ok(Some(lens));
+        };
+
+        lens.command = Some(Command {
+            title: resolve_code_lens(db, file, &target),
+            command: String::new(),
+            arguments: None,
+        });
+
+        Ok(Some(lens))
+    }
+}
+
+impl RetriableRequestHandler for CodeLensResolveRequestHandler {}
+
+fn resolve_data(item: &LspCodeLens) -> Option<CodeLensResolveData> {
+    let data = item.data.clone()?;
+    serde_json::from_value(data).ok()
+}
+
+/// A placeholder URL used when a `codeLens/resolve` request doesn't carry the `data` this
+/// server attaches to every code lens. Looking this up as an open document always fails, which
+/// causes the request to be rejected with a clear `InvalidParams` error instead of panicking.
+fn invalid_url() -> Url {
+    "ty-code-lens-resolve://missing-data"
+        .parse()
+        .expect("static URL is valid")
+}
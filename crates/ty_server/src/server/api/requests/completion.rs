@@ -4,20 +4,34 @@ use std::time::Instant;
 use lsp_types::request::Completion;
 use lsp_types::{
     CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionList,
-    CompletionParams, CompletionResponse, Documentation, TextEdit, Url,
+    CompletionParams, CompletionResponse, Url,
 };
 use ruff_source_file::OneIndexed;
-use ruff_text_size::Ranged;
+use serde::{Deserialize, Serialize};
 use ty_ide::{CompletionKind, completion};
 use ty_project::ProjectDatabase;
 
-use crate::document::{PositionExt, ToRangeExt};
+use crate::document::PositionExt;
 use crate::server::api::traits::{
     BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
 };
 use crate::session::DocumentSnapshot;
 use crate::session::client::Client;
 
+/// Identifies which completion this [`CompletionItem`] came from so that
+/// `completionItem/resolve` can recompute its (expensive) documentation and additional text
+/// edits without having to thread that state through the client round-trip itself.
+///
+/// Recomputing the completion list is cheap in practice: it goes through the same Salsa
+/// queries as the initial request, so as long as the database hasn't changed it's served from
+/// cache.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CompletionResolveData {
+    pub(crate) uri: Url,
+    pub(crate) offset: u32,
+    pub(crate) index: usize,
+}
+
 pub(crate) struct CompletionRequestHandler;
 
 impl RequestHandler for CompletionRequestHandler {
@@ -70,21 +84,12 @@ impl BackgroundDocumentRequestHandler for CompletionRequestHandler {
             .map(|(i, comp)| {
                 let kind = comp.kind(db).map(ty_kind_to_lsp_kind);
                 let type_display = comp.ty.map(|ty| ty.display(db).to_string());
-                let import_edit = comp.import.as_ref().and_then(|edit| {
-                    let range = edit
-                        .range()
-                        .to_lsp_range(db, file, snapshot.encoding())?
-                        .local_range();
-                    Some(TextEdit {
-                        range,
-                        new_text: edit.content().map(ToString::to_string).unwrap_or_default(),
-                    })
-                });
+                let has_import_edit = comp.import.is_some();
 
                 let name = comp.insert.as_deref().unwrap_or(&comp.name).to_string();
                 let import_suffix = comp
                     .module_name
-                    .and_then(|name| import_edit.is_some().then(|| format!(" (import {name})")));
+                    .and_then(|name| has_import_edit.then(|| format!(" (import {name})")));
                 let (label, label_details) = if snapshot
                     .resolved_client_capabilities()
                     .supports_completion_item_label_details()
@@ -100,6 +105,19 @@ impl BackgroundDocumentRequestHandler for CompletionRequestHandler {
                         .unwrap_or_else(|| name);
                     (label, None)
                 };
+
+                // `documentation` and `additional_text_edits` are deliberately left unset here:
+                // rendering a docstring and converting an import `Edit` to an LSP range isn't
+                // free, and we'd otherwise pay that cost for every item in the list instead of
+                // just the one the user highlights. `completionItem/resolve` fills them in on
+                // demand, keyed off of `data`.
+                let data = serde_json::to_value(CompletionResolveData {
+                    uri: snapshot.url().clone(),
+                    offset: offset.into(),
+                    index: i,
+                })
+                .ok();
+
                 CompletionItem {
                     label,
                     kind,
@@ -107,10 +125,7 @@ impl BackgroundDocumentRequestHandler for CompletionRequestHandler {
                     detail: type_display,
                     label_details,
                     insert_text: comp.insert.map(String::from),
-                    additional_text_edits: import_edit.map(|edit| vec![edit]),
-                    documentation: comp
-                        .documentation
-                        .map(|docstring| Documentation::String(docstring.render_plaintext())),
+                    data,
                     ..Default::default()
                 }
             })
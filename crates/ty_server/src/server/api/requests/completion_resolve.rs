@@ -0,0 +1,87 @@
+use std::borrow::Cow;
+
+use lsp_types::{CompletionItem, Documentation, TextEdit, Url};
+use ruff_text_size::{Ranged, TextSize};
+use ty_ide::completion;
+use ty_project::ProjectDatabase;
+
+use crate::document::ToRangeExt;
+use crate::server::api::requests::completion::CompletionResolveData;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct CompletionResolveRequestHandler;
+
+impl RequestHandler for CompletionResolveRequestHandler {
+    type RequestType = lsp_types::request::ResolveCompletionItem;
+}
+
+impl BackgroundDocumentRequestHandler for CompletionResolveRequestHandler {
+    fn document_url(params: &CompletionItem) -> Cow<'_, Url> {
+        resolve_data(params)
+            .map(|data| Cow::Owned(data.uri))
+            .unwrap_or_else(|| Cow::Owned(invalid_url()))
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: CompletionItem,
+    ) -> crate::server::Result<Option<CompletionItem>> {
+        let mut item = params;
+
+        let Some(data) = resolve_data(&item) else {
+            return Ok(Some(item));
+        };
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(Some(item));
+        };
+
+        let settings = snapshot.workspace_settings().completions();
+        let offset = TextSize::from(data.offset);
+        let completions = completion(db, settings, file, offset);
+
+        let Some(comp) = completions.into_iter().nth(data.index) else {
+            return Ok(Some(item));
+        };
+
+        item.documentation = comp
+            .documentation
+            .map(|docstring| Documentation::String(docstring.render_plaintext()));
+
+        item.additional_text_edits = comp.import.as_ref().and_then(|edit| {
+            let range = edit
+                .range()
+                .to_lsp_range(db, file, snapshot.encoding())?
+                .local_range();
+            Some(vec![TextEdit {
+                range,
+                new_text: edit.content().map(ToString::to_string).unwrap_or_default(),
+            }])
+        });
+
+        Ok(Some(item))
+    }
+}
+
+impl RetriableRequestHandler for CompletionResolveRequestHandler {}
+
+fn resolve_data(item: &CompletionItem) -> Option<CompletionResolveData> {
+    let data = item.data.clone()?;
+    serde_json::from_value(data).ok()
+}
+
+/// A placeholder URL used when a `completionItem/resolve` request doesn't carry the `data`
+/// this server attaches to every completion item. Looking this up as an open document always
+/// fails, which causes the request to be rejected with a clear `InvalidParams` error instead
+/// of panicking.
+fn invalid_url() -> Url {
+    "ty-completion-resolve://missing-data"
+        .parse()
+        .expect("static URL is valid")
+}
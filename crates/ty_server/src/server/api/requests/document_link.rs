@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+
+use lsp_types::request::DocumentLinkRequest;
+use lsp_types::{DocumentLink as LspDocumentLink, DocumentLinkParams, Url};
+use ty_ide::document_links;
+use ty_project::ProjectDatabase;
+
+use crate::document::{FileRangeExt, ToRangeExt};
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct DocumentLinkRequestHandler;
+
+impl RequestHandler for DocumentLinkRequestHandler {
+    type RequestType = DocumentLinkRequest;
+}
+
+impl BackgroundDocumentRequestHandler for DocumentLinkRequestHandler {
+    fn document_url(params: &DocumentLinkParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: DocumentLinkParams,
+    ) -> crate::server::Result<Option<Vec<LspDocumentLink>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let links: Vec<_> = document_links(db, file)
+            .into_iter()
+            .filter_map(|link| {
+                let range = link
+                    .range
+                    .to_lsp_range(db, file, snapshot.encoding())?
+                    .local_range();
+                let target = link
+                    .target
+                    .full_file_range()
+                    .to_lsp_range(db, snapshot.encoding())?
+                    .into_location()?
+                    .uri;
+
+                Some(LspDocumentLink {
+                    range,
+                    target: Some(target),
+                    tooltip: None,
+                    data: None,
+                })
+            })
+            .collect();
+
+        if links.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(links))
+        }
+    }
+}
+
+impl RetriableRequestHandler for DocumentLinkRequestHandler {}
@@ -3,6 +3,7 @@ use std::borrow::Cow;
 use lsp_types::request::DocumentSymbolRequest;
 use lsp_types::{DocumentSymbol, DocumentSymbolParams, SymbolInformation, Url};
 use ruff_db::files::File;
+use rustc_hash::FxHashMap;
 use ty_ide::{HierarchicalSymbols, SymbolId, SymbolInfo, document_symbols};
 use ty_project::ProjectDatabase;
 
@@ -72,10 +73,18 @@ impl BackgroundDocumentRequestHandler for DocumentSymbolRequestHandler {
             Ok(Some(lsp_types::DocumentSymbolResponse::Nested(lsp_symbols)))
         } else {
             // Return flattened symbols as SymbolInformation
+            let mut url_cache = FxHashMap::default();
             let lsp_symbols: Vec<SymbolInformation> = symbols
                 .iter()
                 .filter_map(|(_, symbol)| {
-                    convert_to_lsp_symbol_information(db, file, symbol, snapshot.encoding())
+                    convert_to_lsp_symbol_information(
+                        db,
+                        file,
+                        symbol,
+                        snapshot.encoding(),
+                        &mut url_cache,
+                        false,
+                    )
                 })
                 .collect();
 
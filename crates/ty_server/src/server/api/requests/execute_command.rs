@@ -1,4 +1,5 @@
 use crate::capabilities::SupportedCommand;
+use crate::document::DocumentKey;
 use crate::server;
 use crate::server::api::LSPResult;
 use crate::server::api::RequestHandler;
@@ -7,8 +8,10 @@ use crate::session::Session;
 use crate::session::client::Client;
 use lsp_server::ErrorCode;
 use lsp_types::{self as types, request as req};
+use ruff_db::files::system_path_to_file;
 use std::fmt::Write;
 use std::str::FromStr;
+use ty_ide::MarkupKind;
 use ty_project::Db as _;
 
 pub(crate) struct ExecuteCommand;
@@ -20,7 +23,7 @@ impl RequestHandler for ExecuteCommand {
 impl SyncRequestHandler for ExecuteCommand {
     fn run(
         session: &mut Session,
-        _client: &Client,
+        client: &Client,
         params: types::ExecuteCommandParams,
     ) -> server::Result<Option<serde_json::Value>> {
         let command = SupportedCommand::from_str(&params.command)
@@ -30,6 +33,18 @@ impl SyncRequestHandler for ExecuteCommand {
             SupportedCommand::Debug => Ok(Some(serde_json::Value::String(
                 debug_information(session).with_failure_code(ErrorCode::InternalError)?,
             ))),
+            SupportedCommand::RestartServer => {
+                session.restart(client);
+                Ok(None)
+            }
+            SupportedCommand::ClearCaches => {
+                session.clear_caches(client);
+                Ok(None)
+            }
+            SupportedCommand::DumpTypesForFile => Ok(Some(serde_json::Value::String(
+                dump_types_for_file(session, &params.arguments)
+                    .with_failure_code(ErrorCode::InvalidParams)?,
+            ))),
         }
     }
 }
@@ -74,3 +89,44 @@ fn debug_information(session: &Session) -> crate::Result<String> {
     }
     Ok(buffer)
 }
+
+/// Returns a string with the inferred type of every document symbol (nested ones included) in
+/// the file identified by the first command argument, which must be the file's URI.
+fn dump_types_for_file(
+    session: &Session,
+    arguments: &[serde_json::Value],
+) -> crate::Result<String> {
+    let uri = arguments
+        .first()
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("Expected the file URI as the first argument"))?;
+    let url = types::Url::parse(uri)?;
+
+    let path = DocumentKey::from_url(&url).into_file_path();
+    let db = session.project_db(&path);
+
+    let system_path = path
+        .as_system()
+        .ok_or_else(|| anyhow::anyhow!("`{uri}` is not a file on disk"))?;
+    let file = system_path_to_file(db, system_path)
+        .map_err(|err| anyhow::anyhow!("Failed to resolve `{uri}`: {err}"))?;
+
+    let mut buffer = String::new();
+    writeln!(buffer, "Types for {uri}")?;
+    writeln!(buffer)?;
+
+    for (_, symbol) in ty_ide::document_symbols(db, file).iter() {
+        let Some(hover) = ty_ide::hover(db, file, symbol.name_range.start()) else {
+            continue;
+        };
+
+        writeln!(
+            buffer,
+            "{}: {}",
+            symbol.name,
+            hover.display(db, MarkupKind::PlainText)
+        )?;
+    }
+
+    Ok(buffer)
+}
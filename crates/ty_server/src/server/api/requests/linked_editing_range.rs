@@ -0,0 +1,81 @@
+use std::borrow::Cow;
+
+use lsp_types::request::LinkedEditingRangeRequest;
+use lsp_types::{LinkedEditingRangeParams, LinkedEditingRanges, Url};
+use ty_ide::document_highlights;
+use ty_project::ProjectDatabase;
+
+use crate::document::{PositionExt, ToRangeExt};
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct LinkedEditingRangeRequestHandler;
+
+impl RequestHandler for LinkedEditingRangeRequestHandler {
+    type RequestType = LinkedEditingRangeRequest;
+}
+
+impl BackgroundDocumentRequestHandler for LinkedEditingRangeRequestHandler {
+    fn document_url(params: &LinkedEditingRangeParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document_position_params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: LinkedEditingRangeParams,
+    ) -> crate::server::Result<Option<LinkedEditingRanges>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.text_document_position_params.position.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        // Linked editing ranges are, by definition, all of the occurrences of a symbol within
+        // the current document, which is exactly what `document_highlights` already computes.
+        let Some(highlights_result) = document_highlights(db, file, offset) else {
+            return Ok(None);
+        };
+
+        let ranges: Vec<_> = highlights_result
+            .into_iter()
+            .filter_map(|target| {
+                Some(
+                    target
+                        .range()
+                        .to_lsp_range(db, file, snapshot.encoding())?
+                        .local_range(),
+                )
+            })
+            .collect();
+
+        if ranges.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(LinkedEditingRanges {
+                ranges,
+                word_pattern: None,
+            }))
+        }
+    }
+}
+
+impl RetriableRequestHandler for LinkedEditingRangeRequestHandler {}
@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use lsp_types::request::MonikerRequest;
+use lsp_types::{
+    Moniker as LspMoniker, MonikerKind as LspMonikerKind, MonikerParams, UniquenessLevel, Url,
+};
+use ty_ide::{MonikerKind, MonikerUniqueness, monikers};
+use ty_project::ProjectDatabase;
+
+use crate::document::PositionExt;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct MonikerRequestHandler;
+
+impl RequestHandler for MonikerRequestHandler {
+    type RequestType = MonikerRequest;
+}
+
+impl BackgroundDocumentRequestHandler for MonikerRequestHandler {
+    fn document_url(params: &MonikerParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document_position_params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: MonikerParams,
+    ) -> crate::server::Result<Option<Vec<LspMoniker>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.text_document_position_params.position.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(range_info) = monikers(db, file, offset) else {
+            return Ok(None);
+        };
+
+        let monikers: Vec<_> = range_info
+            .value
+            .into_iter()
+            .map(|moniker| LspMoniker {
+                scheme: moniker.scheme.to_string(),
+                identifier: moniker.identifier,
+                unique: match moniker.unique {
+                    MonikerUniqueness::Project => UniquenessLevel::Project,
+                    MonikerUniqueness::Scheme => UniquenessLevel::Scheme,
+                },
+                kind: Some(match moniker.kind {
+                    MonikerKind::Export => LspMonikerKind::Export,
+                    MonikerKind::Import => LspMonikerKind::Import,
+                }),
+            })
+            .collect();
+
+        if monikers.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(monikers))
+        }
+    }
+}
+
+impl RetriableRequestHandler for MonikerRequestHandler {}
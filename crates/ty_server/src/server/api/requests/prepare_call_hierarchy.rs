@@ -0,0 +1,89 @@
+use std::borrow::Cow;
+
+use lsp_types::request::CallHierarchyPrepare;
+use lsp_types::{CallHierarchyItem, CallHierarchyPrepareParams, SymbolKind, Url};
+use ty_ide::prepare_call_hierarchy;
+use ty_project::ProjectDatabase;
+
+use crate::document::{PositionExt, ToLink};
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct PrepareCallHierarchyRequestHandler;
+
+impl RequestHandler for PrepareCallHierarchyRequestHandler {
+    type RequestType = CallHierarchyPrepare;
+}
+
+impl BackgroundDocumentRequestHandler for PrepareCallHierarchyRequestHandler {
+    fn document_url(params: &CallHierarchyPrepareParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document_position_params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: CallHierarchyPrepareParams,
+    ) -> crate::server::Result<Option<Vec<CallHierarchyItem>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.text_document_position_params.position.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(items) = prepare_call_hierarchy(db, file, offset) else {
+            return Ok(None);
+        };
+
+        let items: Vec<_> = items
+            .into_iter()
+            .filter_map(|item| to_lsp_call_hierarchy_item(db, &item, snapshot.encoding()))
+            .collect();
+
+        Ok((!items.is_empty()).then_some(items))
+    }
+}
+
+impl RetriableRequestHandler for PrepareCallHierarchyRequestHandler {}
+
+/// Converts a [`ty_ide::CallHierarchyItem`] into its LSP representation.
+///
+/// Every function and class is reported with [`SymbolKind::FUNCTION`]; the call hierarchy
+/// protocol doesn't distinguish methods or constructors from plain functions in a way that
+/// matters to clients, so there's no need to thread a more specific kind through here.
+pub(super) fn to_lsp_call_hierarchy_item(
+    db: &dyn crate::Db,
+    item: &ty_ide::CallHierarchyItem,
+    encoding: crate::PositionEncoding,
+) -> Option<CallHierarchyItem> {
+    let link = item.target.to_link(db, None, encoding)?;
+
+    Some(CallHierarchyItem {
+        name: item.name.clone(),
+        kind: SymbolKind::FUNCTION,
+        tags: None,
+        detail: None,
+        uri: link.target_uri,
+        range: link.target_range,
+        selection_range: link.target_selection_range,
+        data: None,
+    })
+}
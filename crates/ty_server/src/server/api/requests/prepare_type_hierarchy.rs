@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use lsp_types::request::TypeHierarchyPrepare;
+use lsp_types::{SymbolKind, TypeHierarchyItem, TypeHierarchyPrepareParams, Url};
+use ty_ide::prepare_type_hierarchy;
+use ty_project::ProjectDatabase;
+
+use crate::document::{PositionExt, ToLink};
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct PrepareTypeHierarchyRequestHandler;
+
+impl RequestHandler for PrepareTypeHierarchyRequestHandler {
+    type RequestType = TypeHierarchyPrepare;
+}
+
+impl BackgroundDocumentRequestHandler for PrepareTypeHierarchyRequestHandler {
+    fn document_url(params: &TypeHierarchyPrepareParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document_position_params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: TypeHierarchyPrepareParams,
+    ) -> crate::server::Result<Option<Vec<TypeHierarchyItem>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.text_document_position_params.position.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(items) = prepare_type_hierarchy(db, file, offset) else {
+            return Ok(None);
+        };
+
+        let items: Vec<_> = items
+            .into_iter()
+            .filter_map(|item| to_lsp_type_hierarchy_item(db, &item, snapshot.encoding()))
+            .collect();
+
+        Ok((!items.is_empty()).then_some(items))
+    }
+}
+
+impl RetriableRequestHandler for PrepareTypeHierarchyRequestHandler {}
+
+/// Converts a [`ty_ide::TypeHierarchyItem`] into its LSP representation.
+pub(super) fn to_lsp_type_hierarchy_item(
+    db: &dyn crate::Db,
+    item: &ty_ide::TypeHierarchyItem,
+    encoding: crate::PositionEncoding,
+) -> Option<TypeHierarchyItem> {
+    let link = item.target.to_link(db, None, encoding)?;
+
+    Some(TypeHierarchyItem {
+        name: item.name.clone(),
+        kind: SymbolKind::CLASS,
+        tags: None,
+        detail: None,
+        uri: link.target_uri,
+        range: link.target_range,
+        selection_range: link.target_selection_range,
+        data: None,
+    })
+}
@@ -1,12 +1,15 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 
+use lsp_server::ErrorCode;
 use lsp_types::request::Rename;
 use lsp_types::{RenameParams, TextEdit, Url, WorkspaceEdit};
+use ruff_python_stdlib::identifiers::is_identifier;
 use ty_ide::rename;
 use ty_project::ProjectDatabase;
 
 use crate::document::{PositionExt, ToLink};
+use crate::server::api::LSPResult;
 use crate::server::api::traits::{
     BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
 };
@@ -50,6 +53,14 @@ impl BackgroundDocumentRequestHandler for RenameRequestHandler {
             return Ok(None);
         };
 
+        if !is_identifier(&params.new_name) {
+            return Err(anyhow::anyhow!(
+                "`{}` is not a valid name: it must be a legal Python identifier and not a keyword",
+                params.new_name
+            ))
+            .with_failure_code(ErrorCode::InvalidParams);
+        }
+
         let Some(rename_results) = rename(db, file, offset, &params.new_name) else {
             return Ok(None);
         };
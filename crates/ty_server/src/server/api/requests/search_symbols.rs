@@ -0,0 +1,166 @@
+use std::time::{Duration, Instant};
+
+use lsp_types::SymbolInformation;
+use lsp_types::request::Request;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use ty_ide::{WorkspaceSymbolInfo, workspace_symbols};
+
+use crate::server::api::LSPResult;
+use crate::server::api::symbols::convert_to_lsp_symbol_information;
+use crate::server::api::traits::{
+    BackgroundRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::client::Client;
+use crate::session::tsp::{TspBinaryPayload, TspPayloadEncoding, encode_tsp_messagepack};
+use crate::session::{RequestCancellationToken, SessionSnapshot};
+
+/// A custom `typeServer/searchSymbols` request.
+///
+/// This is a TSP counterpart to the standard `workspace/symbol` request, for clients that speak
+/// the type-server protocol directly rather than full LSP. It shares the same underlying fuzzy
+/// symbol search (and thus the same incrementally-maintained, salsa-backed per-file symbol index)
+/// as [`WorkspaceSymbolRequestHandler`](super::WorkspaceSymbolRequestHandler).
+pub enum SearchSymbols {}
+
+impl Request for SearchSymbols {
+    type Params = SearchSymbolsParams;
+    type Result = SearchSymbolsResult;
+    const METHOD: &'static str = "typeServer/searchSymbols";
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSymbolsParams {
+    /// The fuzzy-match query string.
+    pub query: String,
+
+    /// An optional soft deadline for this request, in milliseconds.
+    ///
+    /// A large workspace can take a while to scan in full. When `timeout_ms` elapses, the
+    /// handler stops after the project it's currently scanning and returns whatever it's
+    /// collected so far with [`SearchSymbolsResult::incomplete`] set, rather than blocking the
+    /// caller until every project has been searched.
+    pub timeout_ms: Option<u64>,
+}
+
+// This is the "returned member lists" case worth checking against borrowed serialization: the
+// symbols here are converted fresh from salsa-interned data in `convert_to_lsp_symbol_information`
+// for every request, and `symbols_binary` is freshly allocated bytes out of `encode_tsp_messagepack`.
+// There's no pre-existing buffer either field could borrow from - `Cow<str>`/borrowed slices help
+// when you're re-serving bytes you already have lying around, not when every response is built
+// from scratch. The actual lever for a large result set is `symbols_binary` itself: MessagePack
+// plus gzip past `GZIP_THRESHOLD_BYTES` cuts the bytes sent, which is the allocation that matters
+// because it's the one that crosses the wire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSymbolsResult {
+    /// The matched symbols, as plain JSON objects.
+    ///
+    /// Empty (and [`Self::symbols_binary`] populated instead) if the client negotiated
+    /// [`TspPayloadEncoding::MessagePack`] via `tspPayloadEncoding` in its initialization
+    /// options. A workspace-wide symbol search is the one TSP response in this server that can
+    /// get large enough for the encoding to matter.
+    ///
+    /// Each symbol's `container_name` is only populated (with the enclosing module's dotted
+    /// name) for clients that declared `"containerName"` in `tspKnownResultFields`; see
+    /// [`crate::session::options::InitializationOptions::tsp_known_result_fields`].
+    pub symbols: Vec<SymbolInformation>,
+
+    /// The same symbols as [`Self::symbols`], MessagePack-encoded (and gzip-compressed, for a
+    /// large enough result set - see [`TspBinaryPayload`]) and base64-wrapped, present only
+    /// when the client negotiated binary TSP payloads.
+    pub symbols_binary: Option<TspBinaryPayload>,
+
+    /// `true` if `timeout_ms` elapsed before every project in the workspace was searched, so
+    /// `symbols`/`symbols_binary` may be missing matches from projects that hadn't been scanned
+    /// yet.
+    pub incomplete: bool,
+}
+
+pub(crate) struct SearchSymbolsRequestHandler;
+
+impl RequestHandler for SearchSymbolsRequestHandler {
+    type RequestType = SearchSymbols;
+}
+
+impl BackgroundRequestHandler for SearchSymbolsRequestHandler {
+    fn run(
+        snapshot: &SessionSnapshot,
+        _client: &Client,
+        params: SearchSymbolsParams,
+        cancellation_token: &RequestCancellationToken,
+    ) -> crate::server::Result<SearchSymbolsResult> {
+        if !snapshot.tsp_enabled() {
+            return Err(anyhow::anyhow!(
+                "the `typeServer/*` facet is disabled for this session"
+            ))
+            .with_failure_code(lsp_server::ErrorCode::MethodNotFound);
+        }
+
+        let deadline = params.timeout_ms.map(|ms| Instant::now() + Duration::from_millis(ms));
+
+        let mut all_symbols = Vec::new();
+        let mut incomplete = false;
+
+        // Iterate through all projects in the session
+        for db in snapshot.projects() {
+            if cancellation_token.is_cancelled() {
+                break;
+            }
+
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                incomplete = true;
+                break;
+            }
+
+            // Each project is a separate Salsa database with its own `File` id space, so the
+            // URL cache is scoped to one project's worth of symbols rather than shared across
+            // the whole request - ids from different projects' databases aren't comparable.
+            let mut url_cache = FxHashMap::default();
+
+            let workspace_symbol_infos = workspace_symbols(db, &params.query);
+            let encoding = snapshot.position_encoding();
+            let include_container_name = snapshot.tsp_supports_container_name();
+
+            for workspace_symbol_info in workspace_symbol_infos {
+                let WorkspaceSymbolInfo { symbol, file } = workspace_symbol_info;
+
+                let Some(symbol) = convert_to_lsp_symbol_information(
+                    db,
+                    file,
+                    symbol,
+                    encoding,
+                    &mut url_cache,
+                    include_container_name,
+                ) else {
+                    tracing::debug!(
+                        "Failed to convert symbol '{}' to LSP symbol information",
+                        file.path(db)
+                    );
+                    continue;
+                };
+
+                all_symbols.push(symbol);
+            }
+        }
+
+        let use_message_pack = snapshot.tsp_payload_encoding() == TspPayloadEncoding::MessagePack;
+        let symbols_binary =
+            use_message_pack.then(|| encode_tsp_messagepack(&all_symbols)).flatten();
+        let symbols = if symbols_binary.is_some() {
+            Vec::new()
+        } else {
+            all_symbols
+        };
+
+        Ok(SearchSymbolsResult {
+            symbols,
+            symbols_binary,
+            incomplete,
+        })
+    }
+}
+
+impl RetriableRequestHandler for SearchSymbolsRequestHandler {}
@@ -5,7 +5,9 @@ use ruff_db::source::source_text;
 use ty_project::ProjectDatabase;
 
 use crate::db::Db;
-use crate::server::api::semantic_tokens::generate_semantic_tokens;
+use crate::server::api::semantic_tokens::{
+    generate_semantic_tokens, next_semantic_tokens_result_id,
+};
 use crate::server::api::traits::{
     BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
 };
@@ -65,8 +67,14 @@ impl BackgroundDocumentRequestHandler for SemanticTokensRequestHandler {
                 .supports_multiline_semantic_tokens(),
         );
 
+        let result_id = next_semantic_tokens_result_id(snapshot.semantic_tokens_result_counter());
+        snapshot.semantic_tokens_cache().lock().unwrap().insert(
+            snapshot.url().clone(),
+            (result_id.clone(), lsp_tokens.clone()),
+        );
+
         Ok(Some(SemanticTokensResult::Tokens(SemanticTokens {
-            result_id: None,
+            result_id: Some(result_id),
             data: lsp_tokens,
         })))
     }
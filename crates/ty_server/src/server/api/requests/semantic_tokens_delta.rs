@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+
+use lsp_types::{
+    SemanticTokens, SemanticTokensDelta, SemanticTokensDeltaParams, SemanticTokensFullDeltaResult,
+    Url,
+};
+use ruff_db::source::source_text;
+use ty_project::ProjectDatabase;
+
+use crate::db::Db;
+use crate::server::api::semantic_tokens::{
+    diff_semantic_tokens, generate_semantic_tokens, next_semantic_tokens_result_id,
+};
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct SemanticTokensDeltaRequestHandler;
+
+impl RequestHandler for SemanticTokensDeltaRequestHandler {
+    type RequestType = lsp_types::request::SemanticTokensFullDeltaRequest;
+}
+
+impl BackgroundDocumentRequestHandler for SemanticTokensDeltaRequestHandler {
+    fn document_url(params: &SemanticTokensDeltaParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.text_document.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: SemanticTokensDeltaParams,
+    ) -> crate::server::Result<Option<SemanticTokensFullDeltaResult>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let mut cell_range = None;
+
+        if snapshot.document().is_cell()
+            && let Some(notebook_document) = db.notebook_document(file)
+            && let Some(notebook) = source_text(db, file).as_notebook()
+        {
+            let cell_index = notebook_document.cell_index_by_uri(snapshot.url());
+
+            cell_range = cell_index.and_then(|index| notebook.cell_range(index));
+        }
+
+        let lsp_tokens = generate_semantic_tokens(
+            db,
+            file,
+            cell_range,
+            snapshot.encoding(),
+            snapshot
+                .resolved_client_capabilities()
+                .supports_multiline_semantic_tokens(),
+        );
+
+        let result_id = next_semantic_tokens_result_id(snapshot.semantic_tokens_result_counter());
+        let previous = {
+            let mut cache = snapshot.semantic_tokens_cache().lock().unwrap();
+            cache.insert(
+                snapshot.url().clone(),
+                (result_id.clone(), lsp_tokens.clone()),
+            )
+        };
+
+        // Only answer with a delta if the client's `previous_result_id` still matches what we
+        // last sent it. Otherwise fall back to a full recompute, which is always a valid
+        // response to this request per the LSP specification.
+        match previous {
+            Some((previous_result_id, previous_tokens))
+                if previous_result_id == params.previous_result_id =>
+            {
+                let edits = diff_semantic_tokens(&previous_tokens, &lsp_tokens);
+
+                Ok(Some(SemanticTokensFullDeltaResult::TokensDelta(
+                    SemanticTokensDelta {
+                        result_id: Some(result_id),
+                        edits,
+                    },
+                )))
+            }
+            _ => Ok(Some(SemanticTokensFullDeltaResult::Tokens(SemanticTokens {
+                result_id: Some(result_id),
+                data: lsp_tokens,
+            }))),
+        }
+    }
+}
+
+impl RetriableRequestHandler for SemanticTokensDeltaRequestHandler {}
@@ -0,0 +1,72 @@
+use std::borrow::Cow;
+
+use lsp_types::request::TypeHierarchySupertypes;
+use lsp_types::{TypeHierarchyItem, TypeHierarchySupertypesParams, Url};
+use ty_ide::{prepare_type_hierarchy, supertypes};
+use ty_project::ProjectDatabase;
+
+use crate::document::PositionExt;
+use crate::server::api::requests::prepare_type_hierarchy::to_lsp_type_hierarchy_item;
+use crate::server::api::traits::{
+    BackgroundDocumentRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::DocumentSnapshot;
+use crate::session::client::Client;
+
+pub(crate) struct TypeHierarchySupertypesRequestHandler;
+
+impl RequestHandler for TypeHierarchySupertypesRequestHandler {
+    type RequestType = TypeHierarchySupertypes;
+}
+
+impl BackgroundDocumentRequestHandler for TypeHierarchySupertypesRequestHandler {
+    fn document_url(params: &TypeHierarchySupertypesParams) -> Cow<'_, Url> {
+        Cow::Borrowed(&params.item.uri)
+    }
+
+    fn run_with_snapshot(
+        db: &ProjectDatabase,
+        snapshot: &DocumentSnapshot,
+        _client: &Client,
+        params: TypeHierarchySupertypesParams,
+    ) -> crate::server::Result<Option<Vec<TypeHierarchyItem>>> {
+        if snapshot
+            .workspace_settings()
+            .is_language_services_disabled()
+        {
+            return Ok(None);
+        }
+
+        let Some(file) = snapshot.to_notebook_or_file(db) else {
+            return Ok(None);
+        };
+
+        let Some(offset) = params.item.selection_range.start.to_text_size(
+            db,
+            file,
+            snapshot.url(),
+            snapshot.encoding(),
+        ) else {
+            return Ok(None);
+        };
+
+        let Some(item) = prepare_type_hierarchy(db, file, offset).and_then(|mut items| {
+            if items.is_empty() {
+                None
+            } else {
+                Some(items.remove(0))
+            }
+        }) else {
+            return Ok(None);
+        };
+
+        let supertypes: Vec<_> = supertypes(db, &item)
+            .into_iter()
+            .filter_map(|supertype| to_lsp_type_hierarchy_item(db, &supertype, snapshot.encoding()))
+            .collect();
+
+        Ok(Some(supertypes))
+    }
+}
+
+impl RetriableRequestHandler for TypeHierarchySupertypesRequestHandler {}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use lsp_types::request::WillRenameFiles;
+use lsp_types::{RenameFilesParams, TextEdit, Url, WorkspaceEdit};
+use ruff_db::files::{FileRange, system_path_to_file};
+use ruff_db::system::SystemPathBuf;
+use ty_ide::rename_module_imports;
+use ty_project::ProjectDatabase;
+use ty_python_semantic::{ModuleName, file_to_module};
+
+use crate::document::FileRangeExt;
+use crate::server::api::traits::{
+    BackgroundRequestHandler, RequestHandler, RetriableRequestHandler,
+};
+use crate::session::client::Client;
+use crate::session::{RequestCancellationToken, SessionSnapshot};
+
+pub(crate) struct WillRenameFilesRequestHandler;
+
+impl RequestHandler for WillRenameFilesRequestHandler {
+    type RequestType = WillRenameFiles;
+}
+
+impl BackgroundRequestHandler for WillRenameFilesRequestHandler {
+    fn run(
+        snapshot: &SessionSnapshot,
+        _client: &Client,
+        params: RenameFilesParams,
+        _cancellation_token: &RequestCancellationToken,
+    ) -> crate::server::Result<Option<WorkspaceEdit>> {
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+
+        for file_rename in &params.files {
+            for db in snapshot.projects() {
+                let Some(edits) = edits_for_renamed_file(db, file_rename) else {
+                    continue;
+                };
+
+                for edit in edits {
+                    let file_range = FileRange::new(edit.file(), edit.range());
+                    let Some(location) = file_range
+                        .to_lsp_range(db, snapshot.position_encoding())
+                        .and_then(|range| range.into_location())
+                    else {
+                        continue;
+                    };
+
+                    changes.entry(location.uri).or_default().push(TextEdit {
+                        range: location.range,
+                        new_text: edit.new_text().to_string(),
+                    });
+                }
+
+                // The renamed file can only belong to a single project.
+                break;
+            }
+        }
+
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(WorkspaceEdit {
+            changes: Some(changes),
+            document_changes: None,
+            change_annotations: None,
+        }))
+    }
+}
+
+impl RetriableRequestHandler for WillRenameFilesRequestHandler {}
+
+/// Computes the edits required to keep imports valid after `file_rename`, if `db`'s project
+/// is the one that owns the renamed file.
+///
+/// Only renames that keep the file in the same directory are supported (i.e. plain module
+/// renames); moving a file to a different directory would require re-resolving the new path
+/// against the project's search paths, which isn't implemented yet.
+fn edits_for_renamed_file(
+    db: &ProjectDatabase,
+    file_rename: &lsp_types::FileRename,
+) -> Option<Vec<ty_ide::ModuleRenameEdit>> {
+    let old_path = Url::parse(&file_rename.old_uri)
+        .ok()?
+        .to_file_path()
+        .ok()
+        .and_then(|path| SystemPathBuf::from_path_buf(path).ok())?;
+    let new_path = Url::parse(&file_rename.new_uri)
+        .ok()?
+        .to_file_path()
+        .ok()
+        .and_then(|path| SystemPathBuf::from_path_buf(path).ok())?;
+
+    if old_path.parent() != new_path.parent() {
+        return None;
+    }
+
+    let old_file = system_path_to_file(db, &old_path).ok()?;
+    let old_module_name = file_to_module(db, old_file)?.name(db).clone();
+
+    let new_stem = new_path.file_stem()?;
+    let new_module_name = match old_module_name.parent() {
+        Some(parent) => ModuleName::new(&format!("{parent}.{new_stem}")),
+        None => ModuleName::new(new_stem),
+    }?;
+
+    rename_module_imports(db, old_file, &new_module_name)
+}
@@ -29,7 +29,9 @@ use crate::server::lazy_work_done_progress::LazyWorkDoneProgress;
 use crate::server::{Action, Result};
 use crate::session::client::Client;
 use crate::session::index::Index;
-use crate::session::{SessionSnapshot, SuspendedWorkspaceDiagnosticRequest};
+use crate::session::{
+    RequestCancellationToken, SessionSnapshot, SuspendedWorkspaceDiagnosticRequest,
+};
 use crate::system::file_to_url;
 
 /// Handler for [Workspace diagnostics](workspace-diagnostics)
@@ -107,6 +109,11 @@ impl BackgroundRequestHandler for WorkspaceDiagnosticRequestHandler {
         snapshot: &SessionSnapshot,
         client: &Client,
         params: WorkspaceDiagnosticParams,
+        // `check_with_reporter` runs each file's check as its own salsa query, so salsa's
+        // own cancellation (triggered when the database is mutated mid-check) already
+        // interrupts this loop; there's no separate loop over already-computed results here
+        // for this token to guard.
+        _cancellation_token: &RequestCancellationToken,
     ) -> Result<WorkspaceDiagnosticReportResult> {
         if !snapshot.global_settings().diagnostic_mode().is_workspace() {
             tracing::debug!("Workspace diagnostics is disabled; returning empty report");
@@ -146,8 +153,9 @@ impl BackgroundRequestHandler for WorkspaceDiagnosticRequestHandler {
         snapshot: SessionSnapshot,
         client: &Client,
         params: WorkspaceDiagnosticParams,
+        cancellation_token: &RequestCancellationToken,
     ) {
-        let result = Self::run(&snapshot, client, params.clone());
+        let result = Self::run(&snapshot, client, params.clone(), cancellation_token);
 
         // Test if this is a no-op result, in which case we should long-poll the request and
         // only respond once some diagnostics have changed to get the latest result ids.
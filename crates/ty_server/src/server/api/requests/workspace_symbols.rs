@@ -1,14 +1,27 @@
+use lsp_types::notification::Notification;
 use lsp_types::request::WorkspaceSymbolRequest;
-use lsp_types::{WorkspaceSymbolParams, WorkspaceSymbolResponse};
+use lsp_types::{ProgressToken, SymbolInformation, WorkspaceSymbolParams, WorkspaceSymbolResponse};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 use ty_ide::{WorkspaceSymbolInfo, workspace_symbols};
 
 use crate::server::api::symbols::convert_to_lsp_symbol_information;
 use crate::server::api::traits::{
     BackgroundRequestHandler, RequestHandler, RetriableRequestHandler,
 };
-use crate::session::SessionSnapshot;
 use crate::session::client::Client;
+use crate::session::{RequestCancellationToken, SessionSnapshot};
 
+/// Handler for `workspace/symbol`.
+///
+/// Projects can have tens of thousands of symbols, so, like
+/// [`WorkspaceDiagnosticRequestHandler`](super::workspace_diagnostic::WorkspaceDiagnosticRequestHandler),
+/// this streams its results back via `$/progress` when the client provides a
+/// `partialResultToken`, flushing one project's matches as soon as they're found instead of
+/// waiting for every project in the workspace to finish. Unlike workspace diagnostics, symbol
+/// search has no result ids or unchanged/full split to track, so there's no need for the
+/// `ResponseWriter`/`ReportingMode` machinery - each project's batch is forwarded as-is.
+/// Clients that don't send a token keep getting the previous single combined response.
 pub(crate) struct WorkspaceSymbolRequestHandler;
 
 impl RequestHandler for WorkspaceSymbolRequestHandler {
@@ -18,14 +31,25 @@ impl RequestHandler for WorkspaceSymbolRequestHandler {
 impl BackgroundRequestHandler for WorkspaceSymbolRequestHandler {
     fn run(
         snapshot: &SessionSnapshot,
-        _client: &Client,
+        client: &Client,
         params: WorkspaceSymbolParams,
+        cancellation_token: &RequestCancellationToken,
     ) -> crate::server::Result<Option<WorkspaceSymbolResponse>> {
         let query = &params.query;
+        let token = params.partial_result_params.partial_result_token;
         let mut all_symbols = Vec::new();
 
         // Iterate through all projects in the session
         for db in snapshot.projects() {
+            if cancellation_token.is_cancelled() {
+                break;
+            }
+
+            // Each project is a separate Salsa database with its own `File` id space, so the
+            // URL cache is scoped to one project's worth of symbols rather than shared across
+            // the whole request - ids from different projects' databases aren't comparable.
+            let mut url_cache = FxHashMap::default();
+
             // Get workspace symbols matching the query
             let start = std::time::Instant::now();
             let workspace_symbol_infos = workspace_symbols(db, query);
@@ -35,6 +59,8 @@ impl BackgroundRequestHandler for WorkspaceSymbolRequestHandler {
                 elapsed = std::time::Instant::now().duration_since(start)
             );
 
+            let mut project_symbols = Vec::with_capacity(workspace_symbol_infos.len());
+
             // Convert to LSP SymbolInformation
             for workspace_symbol_info in workspace_symbol_infos {
                 let WorkspaceSymbolInfo { symbol, file } = workspace_symbol_info;
@@ -42,7 +68,15 @@ impl BackgroundRequestHandler for WorkspaceSymbolRequestHandler {
                 // Get position encoding from session
                 let encoding = snapshot.position_encoding();
 
-                let Some(symbol) = convert_to_lsp_symbol_information(db, file, symbol, encoding)
+                let Some(symbol) =
+                    convert_to_lsp_symbol_information(
+                        db,
+                        file,
+                        symbol,
+                        encoding,
+                        &mut url_cache,
+                        false,
+                    )
                 else {
                     tracing::debug!(
                         "Failed to convert symbol '{}' to LSP symbol information",
@@ -51,10 +85,32 @@ impl BackgroundRequestHandler for WorkspaceSymbolRequestHandler {
                     continue;
                 };
 
-                all_symbols.push(symbol);
+                project_symbols.push(symbol);
+            }
+
+            if project_symbols.is_empty() {
+                continue;
+            }
+
+            if let Some(token) = &token {
+                client.send_notification::<PartialWorkspaceSymbolProgress>(
+                    PartialWorkspaceSymbolProgressParams {
+                        token: token.clone(),
+                        value: project_symbols,
+                    },
+                );
+            } else {
+                all_symbols.append(&mut project_symbols);
             }
         }
 
+        if token.is_some() {
+            // Every match was already streamed via `$/progress` above. The LSP spec treats the
+            // final response as more items appended to what was already streamed, so responding
+            // with `None` here doesn't drop anything - it just closes out the request.
+            return Ok(None);
+        }
+
         if all_symbols.is_empty() {
             Ok(None)
         } else {
@@ -64,3 +120,21 @@ impl BackgroundRequestHandler for WorkspaceSymbolRequestHandler {
 }
 
 impl RetriableRequestHandler for WorkspaceSymbolRequestHandler {}
+
+/// The `$/progress` notification for partial workspace symbol results.
+///
+/// This type is missing in `lsp_types`. That's why we define it here, mirroring
+/// [`PartialWorkspaceProgress`](super::workspace_diagnostic::PartialWorkspaceProgress) which
+/// does the same thing for `workspace/diagnostic`.
+pub struct PartialWorkspaceSymbolProgress;
+
+impl Notification for PartialWorkspaceSymbolProgress {
+    type Params = PartialWorkspaceSymbolProgressParams;
+    const METHOD: &'static str = "$/progress";
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct PartialWorkspaceSymbolProgressParams {
+    pub token: ProgressToken,
+    pub value: Vec<SymbolInformation>,
+}
@@ -1,4 +1,6 @@
-use lsp_types::SemanticToken;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use lsp_types::{SemanticToken, SemanticTokensEdit};
 use ruff_db::source::{line_index, source_text};
 use ruff_source_file::OneIndexed;
 use ruff_text_size::{Ranged, TextRange};
@@ -132,3 +134,100 @@ impl Encoder {
         self.prev_start = start.character;
     }
 }
+
+/// Generates a fresh result id for a `semanticTokens/full` response.
+///
+/// Result ids only need to be unique per session; clients treat them as opaque tokens to
+/// hand back in a later `semanticTokens/full/delta` request.
+pub(crate) fn next_semantic_tokens_result_id(counter: &AtomicU64) -> String {
+    let id = counter.fetch_add(1, Ordering::Relaxed);
+    format!("{id:x}")
+}
+
+/// Computes the edits needed to turn `old` into `new`, expressed as a single replacement of
+/// the tokens between the first and last differing token.
+///
+/// This isn't a minimal diff, but it's a correct one: the LSP specification only requires
+/// that applying `edits` to `old` produces `new`, not that the edit script be optimal.
+pub(crate) fn diff_semantic_tokens(
+    old: &[SemanticToken],
+    new: &[SemanticToken],
+) -> Vec<SemanticTokensEdit> {
+    let prefix_len = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+
+    if prefix_len == old.len() && prefix_len == new.len() {
+        return Vec::new();
+    }
+
+    let suffix_len = old[prefix_len..]
+        .iter()
+        .rev()
+        .zip(new[prefix_len..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_middle_len = old.len() - prefix_len - suffix_len;
+    let new_middle = &new[prefix_len..new.len() - suffix_len];
+
+    vec![SemanticTokensEdit {
+        start: u32::try_from(prefix_len * 5).unwrap(),
+        delete_count: u32::try_from(old_middle_len * 5).unwrap(),
+        data: Some(new_middle.to_vec()),
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(delta_line: u32, delta_start: u32) -> SemanticToken {
+        SemanticToken {
+            delta_line,
+            delta_start,
+            length: 1,
+            token_type: 0,
+            token_modifiers_bitset: 0,
+        }
+    }
+
+    #[test]
+    fn diff_identical_token_streams_produces_no_edits() {
+        let tokens = vec![token(0, 0), token(1, 0), token(0, 4)];
+
+        assert_eq!(diff_semantic_tokens(&tokens, &tokens), Vec::new());
+    }
+
+    #[test]
+    fn diff_appended_token_replaces_only_the_tail() {
+        let old = vec![token(0, 0), token(1, 0)];
+        let new = vec![token(0, 0), token(1, 0), token(0, 4)];
+
+        let edits = diff_semantic_tokens(&old, &new);
+
+        assert_eq!(
+            edits,
+            vec![SemanticTokensEdit {
+                start: 10,
+                delete_count: 0,
+                data: Some(vec![token(0, 4)]),
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_changed_middle_token_replaces_only_that_token() {
+        let old = vec![token(0, 0), token(1, 0), token(0, 4)];
+        let new = vec![token(0, 0), token(1, 5), token(0, 4)];
+
+        let edits = diff_semantic_tokens(&old, &new);
+
+        assert_eq!(
+            edits,
+            vec![SemanticTokensEdit {
+                start: 5,
+                delete_count: 5,
+                data: Some(vec![token(1, 5)]),
+            }]
+        );
+    }
+}
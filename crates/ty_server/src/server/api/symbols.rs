@@ -1,8 +1,10 @@
 //! Utility functions common to language server request handlers
 //! that return symbol information.
 
-use lsp_types::{SymbolInformation, SymbolKind};
+use lsp_types::{SymbolInformation, SymbolKind, Url};
+use rustc_hash::FxHashMap;
 use ty_ide::SymbolInfo;
+use ty_python_semantic::file_to_module;
 
 use crate::Db;
 use crate::document::{PositionEncoding, ToRangeExt};
@@ -29,17 +31,32 @@ pub(crate) fn convert_symbol_kind(kind: ty_ide::SymbolKind) -> SymbolKind {
 ///
 /// Returns `None` if the symbol's range cannot be converted to a location
 /// (e.g., if the file cannot be converted to a URL).
+///
+/// `url_cache` is reused across every symbol in a single workspace-wide search so that files
+/// contributing many matches only pay for the file-to-URL conversion once. See
+/// [`ToRangeExt::to_lsp_range_with_cache`] for why that conversion is worth caching.
+///
+/// `include_container_name` sets `container_name` to the symbol's enclosing module's dotted
+/// name; pass `false` for clients that haven't declared they understand it (see
+/// [`crate::session::options::InitializationOptions::tsp_known_result_fields`]) to skip the
+/// lookup and keep the field `None`.
 pub(crate) fn convert_to_lsp_symbol_information(
     db: &dyn Db,
     file: ruff_db::files::File,
     symbol: SymbolInfo<'_>,
     encoding: PositionEncoding,
+    url_cache: &mut FxHashMap<ruff_db::files::File, Option<Url>>,
+    include_container_name: bool,
 ) -> Option<SymbolInformation> {
     let symbol_kind = convert_symbol_kind(symbol.kind);
     let location = symbol
         .full_range
-        .to_lsp_range(db, file, encoding)?
+        .to_lsp_range_with_cache(db, file, encoding, url_cache)?
         .to_location()?;
+    let container_name = include_container_name
+        .then(|| file_to_module(db, file))
+        .flatten()
+        .map(|module| module.name(db).to_string());
     Some(SymbolInformation {
         name: symbol.name.into_owned(),
         kind: symbol_kind,
@@ -47,6 +64,6 @@ pub(crate) fn convert_to_lsp_symbol_information(
         #[allow(deprecated)]
         deprecated: None,
         location,
-        container_name: None,
+        container_name,
     })
 }
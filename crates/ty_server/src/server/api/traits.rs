@@ -34,7 +34,7 @@
 //! traits in action.
 
 use crate::session::client::Client;
-use crate::session::{DocumentSnapshot, Session, SessionSnapshot};
+use crate::session::{DocumentSnapshot, RequestCancellationToken, Session, SessionSnapshot};
 use lsp_server::RequestId;
 use std::borrow::Cow;
 
@@ -134,10 +134,16 @@ pub(super) trait BackgroundRequestHandler: RetriableRequestHandler {
     ///
     /// This is the main method that handlers implement. It takes the request parameters
     /// from the client and computes the appropriate response data for the LSP request.
+    ///
+    /// `cancellation_token` is signalled if the client cancels the request while it's running.
+    /// Handlers that loop over many projects or files (e.g. a workspace-wide symbol search)
+    /// should check it at each loop boundary and bail out early, since salsa only interrupts
+    /// in-progress query *computation*, not a handler's own loop over already-computed results.
     fn run(
         snapshot: &SessionSnapshot,
         client: &Client,
         params: <<Self as RequestHandler>::RequestType as Request>::Params,
+        cancellation_token: &RequestCancellationToken,
     ) -> super::Result<<<Self as RequestHandler>::RequestType as Request>::Result>;
 
     /// Handles the request lifecycle and sends the response to the client.
@@ -148,8 +154,9 @@ pub(super) trait BackgroundRequestHandler: RetriableRequestHandler {
         snapshot: SessionSnapshot,
         client: &Client,
         params: <<Self as RequestHandler>::RequestType as Request>::Params,
+        cancellation_token: &RequestCancellationToken,
     ) {
-        let result = Self::run(&snapshot, client, params);
+        let result = Self::run(&snapshot, client, params, cancellation_token);
 
         if let Err(err) = &result {
             tracing::error!("An error occurred with request ID {id}: {err}");
@@ -1,7 +1,7 @@
-use crate::server::schedule::Scheduler;
+use crate::server::schedule::{BackgroundSchedule, Scheduler, Task};
 use crate::server::{Server, api};
 use crate::session::client::{Client, ClientResponseHandler};
-use crate::session::{ClientOptions, SuspendedWorkspaceDiagnosticRequest};
+use crate::session::{ClientOptions, Session, SuspendedWorkspaceDiagnosticRequest};
 use anyhow::anyhow;
 use crossbeam::select;
 use lsp_server::Message;
@@ -20,7 +20,12 @@ impl Server {
             self.connection.sender.clone(),
         ));
 
-        let mut scheduler = Scheduler::new(self.worker_threads);
+        let tsp_worker_threads = self
+            .session
+            .initialization_options()
+            .tsp_worker_threads
+            .unwrap_or(std::num::NonZeroUsize::new(1).unwrap());
+        let mut scheduler = Scheduler::new(self.worker_threads, tsp_worker_threads);
 
         while let Ok(next_event) = self.next_event() {
             let Some(next_event) = next_event else {
@@ -45,6 +50,26 @@ impl Server {
                                 .incoming_mut()
                                 .register(req.id.clone(), req.method.clone());
 
+                            if let Some(max_pending) = self
+                                .session
+                                .initialization_options()
+                                .max_pending_requests
+                            {
+                                let pending = self.session.request_queue().incoming().len();
+                                if pending > max_pending {
+                                    tracing::warn!(
+                                        "Rejecting request `{}` because {max_pending} requests are already pending",
+                                        &req.method
+                                    );
+                                    self.session.request_queue_mut().incoming_mut().complete(&req.id);
+                                    client.respond_err(
+                                        req.id,
+                                        server_busy_error(pending, max_pending),
+                                    );
+                                    continue;
+                                }
+                            }
+
                             if self.session.is_shutdown_requested() {
                                 tracing::warn!(
                                     "Received request `{}` after server shutdown was requested, discarding",
@@ -61,6 +86,10 @@ impl Server {
                                 continue;
                             }
 
+                            self.session
+                                .middleware()
+                                .before_dispatch(&req.method, &req.params);
+
                             api::request(req)
                         }
                         Message::Notification(notification) => {
@@ -100,6 +129,26 @@ impl Server {
 
                     scheduler.dispatch(task, &mut self.session, client);
                 }
+                Event::DiagnosticsDebounceElapsed => {
+                    for url in self.session.take_due_diagnostics_publishes() {
+                        let Ok(document) = self.session.document_handle(&url) else {
+                            // The document was closed before its debounce window elapsed.
+                            continue;
+                        };
+
+                        api::publish_diagnostics(&document, &self.session, &client);
+                    }
+                }
+                Event::Idle => {
+                    tracing::info!(
+                        "No messages received for {} minutes, shutting down",
+                        self.session
+                            .initialization_options()
+                            .idle_timeout_minutes
+                            .unwrap_or_default()
+                    );
+                    return Ok(());
+                }
                 Event::Action(action) => match action {
                     Action::SendResponse(response) => {
                         // Filter out responses for already canceled requests.
@@ -111,6 +160,21 @@ impl Server {
                         {
                             let duration = start_time.elapsed();
                             tracing::trace!(name: "message response", method, %response.id, duration = format_args!("{:0.2?}", duration));
+                            self.session.middleware().after_respond(&method, duration);
+
+                            if let Some(timeout) = self
+                                .session
+                                .initialization_options()
+                                .request_timeout_seconds
+                                .map(std::time::Duration::from_secs)
+                            {
+                                if duration > timeout {
+                                    tracing::warn!(
+                                        "Request `{method}` ({}) took {duration:0.2?}, exceeding the configured timeout of {timeout:0.2?}",
+                                        response.id
+                                    );
+                                }
+                            }
 
                             self.connection.sender.send(Message::Response(response))?;
                         } else {
@@ -150,12 +214,63 @@ impl Server {
                     }
 
                     Action::InitializeWorkspaces(workspaces_with_options) => {
+                        // `initialize_workspaces` registers the file watcher (and other dynamic
+                        // capabilities) after the workspaces are set up, so that the watcher
+                        // globs can take the projects' search paths into account.
                         self.session
                             .initialize_workspaces(workspaces_with_options, &client);
-                        // We do this here after workspaces have been initialized
-                        // so that the file watcher globs can take project search
-                        // paths into account.
-                        // self.try_register_file_watcher(&client);
+                    }
+
+                    Action::SetBackgroundWorkerThreads(threads) => {
+                        scheduler.set_background_worker_threads(threads);
+                    }
+
+                    Action::WarmDocument(url) => {
+                        let task = Task::background(
+                            BackgroundSchedule::Worker,
+                            move |session: &Session| {
+                                let Ok(document) = session.snapshot_document(&url) else {
+                                    return Box::new(|_client: &Client| {});
+                                };
+
+                                let path = document.notebook_or_file_path();
+                                let db = session.project_db(path).clone();
+
+                                Box::new(move |_client: &Client| {
+                                    let _span =
+                                        tracing::debug_span!("warm_document", %url).entered();
+                                    salsa::attach(&db, || {
+                                        api::warm_document(&db, document.document());
+                                    });
+                                })
+                            },
+                        );
+                        scheduler.dispatch(task, &mut self.session, client);
+                    }
+
+                    Action::ReloadWorkspaces(workspaces_with_options) => {
+                        self.session
+                            .reload_workspaces(workspaces_with_options, &client);
+
+                        if self
+                            .session
+                            .client_capabilities()
+                            .supports_workspace_diagnostic_refresh()
+                        {
+                            client.send_request::<lsp_types::request::WorkspaceDiagnosticRefresh>(
+                                &self.session,
+                                (),
+                                |_, ()| {},
+                            );
+                        } else {
+                            for document in self.session.text_document_handles().collect::<Vec<_>>() {
+                                api::publish_diagnostics_if_needed(
+                                    &document,
+                                    &self.session,
+                                    &client,
+                                );
+                            }
+                        }
                     }
                 },
             }
@@ -186,12 +301,30 @@ impl Server {
             return Ok(Some(Event::Message(deferred)));
         }
 
+        // `crossbeam::channel::never()` never becomes ready, so the idle branch below is
+        // effectively disabled when idle shutdown isn't configured.
+        let idle_timer = match self.session.initialization_options().idle_timeout_minutes {
+            Some(minutes) => crossbeam::channel::after(std::time::Duration::from_secs(
+                minutes * 60,
+            )),
+            None => crossbeam::channel::never(),
+        };
+
+        // Likewise, `crossbeam::channel::never()` disables the debounce branch when no
+        // documents have a pending debounced `publishDiagnostics` notification.
+        let diagnostics_debounce_timer = match self.session.next_diagnostics_publish_deadline() {
+            Some(deadline) => crossbeam::channel::after(deadline),
+            None => crossbeam::channel::never(),
+        };
+
         select!(
             recv(self.connection.receiver) -> msg => {
                 // Ignore disconnect errors, they're handled by the main loop (it will exit).
                 Ok(msg.ok().map(Event::Message))
             },
             recv(self.main_loop_receiver) -> event => event.map(Some),
+            recv(idle_timer) -> _ => Ok(Some(Event::Idle)),
+            recv(diagnostics_debounce_timer) -> _ => Ok(Some(Event::DiagnosticsDebounceElapsed)),
         )
     }
 
@@ -285,6 +418,42 @@ impl Server {
     }
 }
 
+/// Structured `data` payload attached to the `Server busy` error, so that clients that bother to
+/// look don't have to guess a backoff from the message string alone.
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerBusyData {
+    /// How many requests are currently pending, including the one being rejected.
+    pending_requests: usize,
+
+    /// The `maxPendingRequests` threshold that was exceeded.
+    max_pending_requests: usize,
+
+    /// A hint for how long the client should wait before retrying, in milliseconds.
+    ///
+    /// This is deliberately crude (proportional to how far over the threshold the queue is,
+    /// clamped to a sane range) rather than based on actual queue-drain-rate measurements, which
+    /// this server doesn't track.
+    retry_after_ms: u64,
+}
+
+fn server_busy_error(pending: usize, max_pending: usize) -> lsp_server::ResponseError {
+    let over_by = u64::try_from(pending.saturating_sub(max_pending)).unwrap_or(u64::MAX);
+    let retry_after_ms = (over_by.saturating_mul(50) + 100).min(2_000);
+
+    lsp_server::ResponseError {
+        // `-32000` is the start of the JSON-RPC reserved "server error" range.
+        code: -32000,
+        message: "Server busy: too many pending requests".to_owned(),
+        data: serde_json::to_value(ServerBusyData {
+            pending_requests: pending,
+            max_pending_requests: max_pending,
+            retry_after_ms,
+        })
+        .ok(),
+    }
+}
+
 /// An action that should be performed on the main loop.
 #[derive(Debug)]
 pub(crate) enum Action {
@@ -302,6 +471,27 @@ pub(crate) enum Action {
     /// Initialize the workspace after the server received
     /// the options from the client.
     InitializeWorkspaces(Vec<(Url, ClientOptions)>),
+
+    /// Reload the settings of already-initialized workspaces after the server received the
+    /// updated options from the client in response to a `workspace/didChangeConfiguration`
+    /// notification.
+    ReloadWorkspaces(Vec<(Url, ClientOptions)>),
+
+    /// Grow the background worker pool to the given number of threads.
+    ///
+    /// Issued by [`ServerHandle::set_background_worker_threads`], which embedders can use to
+    /// adapt to machine load at runtime instead of restarting the server (and losing all of
+    /// its session state).
+    ///
+    /// [`ServerHandle::set_background_worker_threads`]: crate::server::ServerHandle::set_background_worker_threads
+    SetBackgroundWorkerThreads(std::num::NonZeroUsize),
+
+    /// Opportunistically warm the Salsa caches for a just-opened document on a worker thread.
+    ///
+    /// Issued for documents whose diagnostics aren't computed eagerly on open (clients that pull
+    /// diagnostics instead of receiving them pushed), so the cost of the first real inference
+    /// doesn't land on whichever request the user happens to make first.
+    WarmDocument(Url),
 }
 
 #[derive(Debug)]
@@ -310,6 +500,16 @@ pub(crate) enum Event {
     Message(lsp_server::Message),
 
     Action(Action),
+
+    /// A debounced `publishDiagnostics` notification is now due.
+    ///
+    /// See `diagnosticsDebounceMs` in [`InitializationOptions`](crate::session::InitializationOptions).
+    DiagnosticsDebounceElapsed,
+
+    /// No message was received from the client for the configured idle timeout.
+    ///
+    /// See `idleTimeoutMinutes` in [`InitializationOptions`](crate::session::InitializationOptions).
+    Idle,
 }
 
 pub(crate) struct SendRequest {
@@ -0,0 +1,63 @@
+//! Middleware hooks around TSP (`typeServer/*` and regular LSP) request processing.
+//!
+//! Embedders that need to add cross-cutting behavior such as auth checks, auditing, or
+//! telemetry can implement [`TspMiddleware`] and register it via
+//! [`ServerBuilder::with_middleware`] instead of forking the main loop. Hooks run
+//! synchronously on the main-loop thread, so they should be cheap; expensive work (e.g.
+//! writing to a remote audit log) should hand off to a background task.
+//!
+//! [`ServerBuilder::with_middleware`]: crate::ServerBuilder::with_middleware
+
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// A hook invoked before a request is dispatched to its handler, and after the response
+/// has been sent.
+///
+/// Implementations are shared across all requests for the lifetime of the session, so they
+/// must be `Send + Sync`.
+pub trait TspMiddleware: Send + Sync {
+    /// Called right before a request is handed off to its handler.
+    ///
+    /// `params` is the raw, not-yet-deserialized request payload.
+    fn before_dispatch(&self, method: &str, params: &Value) {
+        let _ = (method, params);
+    }
+
+    /// Called after the response for a request has been queued for sending.
+    ///
+    /// `duration` is the time elapsed between the request being received and its response
+    /// being queued.
+    fn after_respond(&self, method: &str, duration: Duration) {
+        let _ = (method, duration);
+    }
+}
+
+/// An ordered chain of [`TspMiddleware`] hooks.
+///
+/// Hooks run in registration order for `before_dispatch` and in the same order for
+/// `after_respond`; middleware shouldn't assume any relative ordering between different
+/// requests, only within a single request's lifecycle.
+#[derive(Default)]
+pub(crate) struct MiddlewareChain {
+    middleware: Vec<Box<dyn TspMiddleware>>,
+}
+
+impl MiddlewareChain {
+    pub(crate) fn register(&mut self, middleware: Box<dyn TspMiddleware>) {
+        self.middleware.push(middleware);
+    }
+
+    pub(crate) fn before_dispatch(&self, method: &str, params: &Value) {
+        for middleware in &self.middleware {
+            middleware.before_dispatch(method, params);
+        }
+    }
+
+    pub(crate) fn after_respond(&self, method: &str, duration: Duration) {
+        for middleware in &self.middleware {
+            middleware.after_respond(method, duration);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::num::NonZeroUsize;
+use std::panic::AssertUnwindSafe;
 
 use crate::session::Session;
 
@@ -33,30 +34,67 @@ pub(crate) fn spawn_main_loop(
 pub(crate) struct Scheduler {
     fmt_pool: thread::Pool,
     background_pool: thread::Pool,
+    tsp_pool: thread::Pool,
 }
 
 impl Scheduler {
-    pub(super) fn new(worker_threads: NonZeroUsize) -> Self {
+    pub(super) fn new(worker_threads: NonZeroUsize, tsp_worker_threads: NonZeroUsize) -> Self {
         const FMT_THREADS: usize = 1;
         Self {
             fmt_pool: thread::Pool::new(NonZeroUsize::try_from(FMT_THREADS).unwrap()),
             background_pool: thread::Pool::new(worker_threads),
+            tsp_pool: thread::Pool::new(tsp_worker_threads),
         }
     }
 
+    /// Grows the background worker pool to `threads` worker threads, so embedders can adapt to
+    /// machine load without restarting the server and losing all of its session state.
+    ///
+    /// See [`thread::Pool::set_size`] for why this can only grow the pool, not shrink it.
+    pub(super) fn set_background_worker_threads(&mut self, threads: NonZeroUsize) {
+        self.background_pool.set_size(threads);
+    }
+
     /// Dispatches a `task` by either running it as a blocking function or
     /// executing it on a background thread pool.
+    ///
+    /// A panic while running or building the task is caught and logged rather than taking
+    /// down the main loop: one bad request shouldn't wedge the whole session. Handlers that
+    /// can identify the request that caused the panic (see [`api::panic_response`]) still
+    /// respond to the client with an `InternalError`; this is a last-resort backstop for
+    /// panics that happen outside of that machinery, e.g. while building the background task
+    /// itself.
+    ///
+    /// [`api::panic_response`]: super::api
     pub(super) fn dispatch(&mut self, task: task::Task, session: &mut Session, client: Client) {
         match task {
             Task::Sync(SyncTask { func }) => {
-                func(session, &client);
+                if let Err(payload) =
+                    std::panic::catch_unwind(AssertUnwindSafe(|| func(session, &client)))
+                {
+                    log_task_panic("Synchronous task", &payload);
+                }
             }
             Task::Background(BackgroundTaskBuilder {
                 schedule,
                 builder: func,
             }) => {
-                let static_func = func(session);
-                let task = move || static_func(&client);
+                let static_func = match std::panic::catch_unwind(AssertUnwindSafe(|| func(session)))
+                {
+                    Ok(static_func) => static_func,
+                    Err(payload) => {
+                        log_task_panic("Background task setup", &payload);
+                        return;
+                    }
+                };
+
+                let task = move || {
+                    if let Err(payload) =
+                        std::panic::catch_unwind(AssertUnwindSafe(|| static_func(&client)))
+                    {
+                        log_task_panic("Background task", &payload);
+                    }
+                };
                 match schedule {
                     BackgroundSchedule::Worker => {
                         self.background_pool.spawn(ThreadPriority::Worker, task);
@@ -67,8 +105,23 @@ impl Scheduler {
                     BackgroundSchedule::Fmt => {
                         self.fmt_pool.spawn(ThreadPriority::LatencySensitive, task);
                     }
+                    BackgroundSchedule::Tsp => {
+                        self.tsp_pool.spawn(ThreadPriority::Worker, task);
+                    }
                 }
             }
         }
     }
 }
+
+/// Logs a caught task panic at the error level, extracting a human-readable message from the
+/// panic payload where possible.
+fn log_task_panic(context: &str, payload: &(dyn std::any::Any + Send)) {
+    if let Some(msg) = payload.downcast_ref::<String>() {
+        tracing::error!("{context} panicked with: {msg}; recovering and continuing");
+    } else if let Some(msg) = payload.downcast_ref::<&str>() {
+        tracing::error!("{context} panicked with: {msg}; recovering and continuing");
+    } else {
+        tracing::error!("{context} panicked; recovering and continuing");
+    }
+}
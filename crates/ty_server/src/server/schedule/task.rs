@@ -24,6 +24,9 @@ pub(in crate::server) enum BackgroundSchedule {
     /// The default for any request that isn't in the critical path of the user typing.
     #[default]
     Worker,
+    /// The task should be run on the dedicated pool for `typeServer/*` (TSP) traffic, so
+    /// that it doesn't compete with regular LSP requests for worker threads.
+    Tsp,
 }
 
 /// A [`Task`] is a future that has not yet started, and it is the job of
@@ -34,8 +34,11 @@ pub(crate) struct Pool {
     // so that the channel is actually closed
     // before we join the worker threads!
     job_sender: Sender<Job>,
+    // Kept around so that `set_size` can spawn additional workers sharing the same queue.
+    job_receiver: Receiver<Job>,
     _handles: Vec<JoinHandle>,
     extant_tasks: Arc<AtomicUsize>,
+    threads: NonZeroUsize,
 }
 
 struct Job {
@@ -43,76 +46,82 @@ struct Job {
     f: Box<dyn FnOnce() + Send + 'static>,
 }
 
+// Override OS defaults to avoid stack overflows on platforms with low stack size defaults.
+const STACK_SIZE: usize = 2 * 1024 * 1024;
+const INITIAL_PRIORITY: ThreadPriority = ThreadPriority::Worker;
+
 impl Pool {
     pub(crate) fn new(threads: NonZeroUsize) -> Pool {
-        // Override OS defaults to avoid stack overflows on platforms with low stack size defaults.
-        const STACK_SIZE: usize = 2 * 1024 * 1024;
-        const INITIAL_PRIORITY: ThreadPriority = ThreadPriority::Worker;
-
-        let threads = usize::from(threads);
+        let thread_count = usize::from(threads);
 
-        let (job_sender, job_receiver) = crossbeam::channel::bounded(std::cmp::min(threads * 2, 4));
+        let (job_sender, job_receiver) =
+            crossbeam::channel::bounded(std::cmp::min(thread_count * 2, 4));
         let extant_tasks = Arc::new(AtomicUsize::new(0));
 
-        let mut handles = Vec::with_capacity(threads);
-        for i in 0..threads {
-            let handle = Builder::new(INITIAL_PRIORITY)
-                .stack_size(STACK_SIZE)
-                .name(format!("ty:worker:{i}"))
-                .spawn({
-                    let extant_tasks = Arc::clone(&extant_tasks);
-                    let job_receiver: Receiver<Job> = job_receiver.clone();
-                    move || {
-                        let mut current_priority = INITIAL_PRIORITY;
-                        for job in job_receiver {
-                            if job.requested_priority != current_priority {
-                                job.requested_priority.apply_to_current_thread();
-                                current_priority = job.requested_priority;
-                            }
-                            extant_tasks.fetch_add(1, Ordering::SeqCst);
-
-                            // SAFETY: it's safe to assume that `job.f` is unwind safe because we always
-                            // abort the process if it panics.
-                            // Panicking here ensures that we don't swallow errors and is the same as
-                            // what rayon does.
-                            // Any recovery should be implemented outside the thread pool (e.g. when
-                            // dispatching requests/notifications etc).
-                            if let Err(error) = std::panic::catch_unwind(AssertUnwindSafe(job.f)) {
-                                if let Some(msg) = error.downcast_ref::<String>() {
-                                    tracing::error!("Worker thread panicked with: {msg}; aborting");
-                                } else if let Some(msg) = error.downcast_ref::<&str>() {
-                                    tracing::error!("Worker thread panicked with: {msg}; aborting");
-                                } else if let Some(cancelled) =
-                                    error.downcast_ref::<salsa::Cancelled>()
-                                {
-                                    tracing::error!(
-                                        "Worker thread got cancelled: {cancelled}; aborting"
-                                    );
-                                } else {
-                                    tracing::error!(
-                                        "Worker thread panicked with: {error:?}; aborting"
-                                    );
-                                }
-
-                                std::process::abort();
-                            }
-
-                            extant_tasks.fetch_sub(1, Ordering::SeqCst);
-                        }
-                    }
-                })
-                .expect("failed to spawn thread");
-
-            handles.push(handle);
-        }
+        let handles = (0..thread_count)
+            .map(|i| Self::spawn_worker(i, &job_receiver, &extant_tasks))
+            .collect();
 
         Pool {
             _handles: handles,
             extant_tasks,
             job_sender,
+            job_receiver,
+            threads,
         }
     }
 
+    fn spawn_worker(
+        index: usize,
+        job_receiver: &Receiver<Job>,
+        extant_tasks: &Arc<AtomicUsize>,
+    ) -> JoinHandle {
+        Builder::new(INITIAL_PRIORITY)
+            .stack_size(STACK_SIZE)
+            .name(format!("ty:worker:{index}"))
+            .spawn({
+                let extant_tasks = Arc::clone(extant_tasks);
+                let job_receiver = job_receiver.clone();
+                move || {
+                    let mut current_priority = INITIAL_PRIORITY;
+                    for job in job_receiver {
+                        if job.requested_priority != current_priority {
+                            job.requested_priority.apply_to_current_thread();
+                            current_priority = job.requested_priority;
+                        }
+                        extant_tasks.fetch_add(1, Ordering::SeqCst);
+
+                        // SAFETY: it's safe to assume that `job.f` is unwind safe because we always
+                        // abort the process if it panics.
+                        // Panicking here ensures that we don't swallow errors and is the same as
+                        // what rayon does.
+                        // Any recovery should be implemented outside the thread pool (e.g. when
+                        // dispatching requests/notifications etc).
+                        if let Err(error) = std::panic::catch_unwind(AssertUnwindSafe(job.f)) {
+                            if let Some(msg) = error.downcast_ref::<String>() {
+                                tracing::error!("Worker thread panicked with: {msg}; aborting");
+                            } else if let Some(msg) = error.downcast_ref::<&str>() {
+                                tracing::error!("Worker thread panicked with: {msg}; aborting");
+                            } else if let Some(cancelled) =
+                                error.downcast_ref::<salsa::Cancelled>()
+                            {
+                                tracing::error!(
+                                    "Worker thread got cancelled: {cancelled}; aborting"
+                                );
+                            } else {
+                                tracing::error!("Worker thread panicked with: {error:?}; aborting");
+                            }
+
+                            std::process::abort();
+                        }
+
+                        extant_tasks.fetch_sub(1, Ordering::SeqCst);
+                    }
+                }
+            })
+            .expect("failed to spawn thread")
+    }
+
     pub(crate) fn spawn<F>(&self, priority: ThreadPriority, f: F)
     where
         F: FnOnce() + Send + 'static,
@@ -131,6 +140,34 @@ impl Pool {
         self.job_sender.send(job).unwrap();
     }
 
+    /// Grows the pool to `threads` worker threads.
+    ///
+    /// New workers are just another consumer of the existing job channel, so growing is cheap
+    /// and safe to do between requests. Shrinking isn't implemented: workers only exit once
+    /// *every* sender and receiver for the job channel is dropped, which would tear down the
+    /// whole pool rather than a chosen subset of it. Supporting a partial shrink would mean
+    /// giving each worker its own shutdown signal to check between jobs (e.g. an `AtomicBool`
+    /// or a second "stop" channel) - a bigger change than this pool's current design, so for
+    /// now a request to shrink is logged and otherwise ignored.
+    pub(crate) fn set_size(&mut self, threads: NonZeroUsize) {
+        if threads <= self.threads {
+            if threads < self.threads {
+                tracing::debug!(
+                    "Ignoring request to shrink the pool from {} to {} threads; shrinking isn't supported",
+                    self.threads,
+                    threads
+                );
+            }
+            return;
+        }
+
+        for i in usize::from(self.threads)..usize::from(threads) {
+            self._handles
+                .push(Self::spawn_worker(i, &self.job_receiver, &self.extant_tasks));
+        }
+        self.threads = threads;
+    }
+
     #[expect(dead_code)]
     pub(super) fn len(&self) -> usize {
         self.extant_tasks.load(Ordering::SeqCst)
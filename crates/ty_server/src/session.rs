@@ -3,7 +3,9 @@
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::ops::{Deref, DerefMut};
 use std::panic::RefUnwindSafe;
-use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, anyhow};
 use lsp_server::{Message, RequestId};
@@ -14,13 +16,14 @@ use lsp_types::request::{
 };
 use lsp_types::{
     DiagnosticRegistrationOptions, DiagnosticServerCapabilities,
-    DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, Registration, RegistrationParams,
-    TextDocumentContentChangeEvent, Unregistration, UnregistrationParams, Url,
+    DidChangeWatchedFilesRegistrationOptions, FileSystemWatcher, Range, Registration,
+    RegistrationParams, TextDocumentContentChangeEvent, Unregistration, UnregistrationParams, Url,
 };
 use ruff_db::Db;
 use ruff_db::files::{File, system_path_to_file};
 use ruff_db::system::{System, SystemPath, SystemPathBuf};
 use ruff_python_ast::PySourceType;
+use rustc_hash::FxHashMap;
 use ty_combine::Combine;
 use ty_project::metadata::Options;
 use ty_project::watch::{ChangeEvent, CreatedKind};
@@ -32,6 +35,7 @@ use ty_python_semantic::MisconfigurationMode;
 
 pub(crate) use self::options::InitializationOptions;
 pub use self::options::{ClientOptions, DiagnosticMode};
+pub(crate) use self::request_queue::RequestCancellationToken;
 pub(crate) use self::settings::{GlobalSettings, WorkspaceSettings};
 use crate::capabilities::{ResolvedClientCapabilities, server_diagnostic_options};
 use crate::document::{DocumentKey, DocumentVersion, NotebookDocument};
@@ -48,6 +52,7 @@ pub(crate) mod index;
 mod options;
 mod request_queue;
 mod settings;
+pub mod tsp;
 
 /// The global state for the LSP
 pub(crate) struct Session {
@@ -113,8 +118,42 @@ pub(crate) struct Session {
     /// Registrations is a set of LSP methods that have been dynamically registered with the
     /// client.
     registrations: HashSet<String>,
+
+    /// Middleware hooks run before dispatching and after responding to a request. See
+    /// [`MiddlewareChain`] for details.
+    middleware: crate::server::middleware::MiddlewareChain,
+
+    /// Caches the most recently computed semantic tokens per document so that
+    /// `textDocument/semanticTokens/full/delta` requests can respond with an edit script
+    /// instead of recomputing and re-sending the entire token stream.
+    semantic_tokens_cache: SemanticTokensCache,
+
+    /// Generates the result ids handed out alongside semantic tokens responses. Shared so
+    /// that every result id handed out by this session is unique.
+    semantic_tokens_result_counter: Arc<AtomicU64>,
+
+    /// Deadlines for documents whose `publishDiagnostics` notification has been debounced (see
+    /// `diagnosticsDebounceMs`), keyed by the document's URL.
+    ///
+    /// Inserted into by `schedule_diagnostics_publish` on every edit; the main loop's timer
+    /// drains whichever entries are due via `take_due_diagnostics_publishes`, so that a burst of
+    /// edits to the same document collapses into a single publish once it settles.
+    pending_diagnostics_publishes: FxHashMap<Url, Instant>,
+
+    /// The most recently reported visible line ranges per document, from `typeServer/visibleRanges`
+    /// notifications.
+    ///
+    /// A document present in this map (with a non-empty range list) is one the client reports as
+    /// currently on-screen; see `take_due_diagnostics_publishes` for the one place this currently
+    /// affects scheduling.
+    visible_ranges: FxHashMap<Url, Vec<Range>>,
 }
 
+/// Maps a document URL to the result id and token stream of the last `semanticTokens/full`
+/// (or `/full/delta`) response sent for it.
+pub(crate) type SemanticTokensCache =
+    Arc<Mutex<FxHashMap<Url, (String, Vec<lsp_types::SemanticToken>)>>>;
+
 /// LSP State for a Project
 pub(crate) struct ProjectState {
     /// Files that we have outstanding otherwise-untracked pushed diagnostics for.
@@ -176,9 +215,30 @@ impl Session {
             suspended_workspace_diagnostics_request: None,
             revision: 0,
             registrations: HashSet::new(),
+            middleware: crate::server::middleware::MiddlewareChain::default(),
+            semantic_tokens_cache: Arc::new(Mutex::new(FxHashMap::default())),
+            semantic_tokens_result_counter: Arc::new(AtomicU64::new(0)),
+            pending_diagnostics_publishes: FxHashMap::default(),
+            visible_ranges: FxHashMap::default(),
         })
     }
 
+    /// Registers a [`TspMiddleware`](crate::server::middleware::TspMiddleware) hook to run
+    /// around every request the server processes.
+    ///
+    /// Called from [`ServerBuilder::build`](crate::ServerBuilder::build) for each hook an
+    /// embedder registered via [`ServerBuilder::with_middleware`](crate::ServerBuilder::with_middleware).
+    pub(crate) fn register_middleware(
+        &mut self,
+        middleware: Box<dyn crate::server::middleware::TspMiddleware>,
+    ) {
+        self.middleware.register(middleware);
+    }
+
+    pub(crate) fn middleware(&self) -> &crate::server::middleware::MiddlewareChain {
+        &self.middleware
+    }
+
     pub(crate) fn request_queue(&self) -> &RequestQueue {
         &self.request_queue
     }
@@ -238,6 +298,60 @@ impl Session {
             });
     }
 
+    /// Schedules (or reschedules, extending the debounce window) a `publishDiagnostics`
+    /// notification for `url`, to be sent once `debounce` elapses without another edit.
+    pub(crate) fn schedule_diagnostics_publish(&mut self, url: Url, debounce: Duration) {
+        self.pending_diagnostics_publishes
+            .insert(url, Instant::now() + debounce);
+    }
+
+    /// Returns the duration until the next debounced `publishDiagnostics` notification is due,
+    /// or `None` if none are pending.
+    pub(crate) fn next_diagnostics_publish_deadline(&self) -> Option<Duration> {
+        self.pending_diagnostics_publishes
+            .values()
+            .min()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Removes and returns the URLs of all documents whose debounce window has elapsed.
+    ///
+    /// Documents the client currently has visible (see `set_visible_ranges`) are ordered first,
+    /// so that a burst of edits across several open documents reports back on the one the user is
+    /// looking at before the ones sitting in background tabs.
+    pub(crate) fn take_due_diagnostics_publishes(&mut self) -> Vec<Url> {
+        let now = Instant::now();
+        let mut due: Vec<Url> = self
+            .pending_diagnostics_publishes
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(url, _)| url.clone())
+            .collect();
+
+        for url in &due {
+            self.pending_diagnostics_publishes.remove(url);
+        }
+
+        due.sort_by_key(|url| !self.is_visible(url));
+
+        due
+    }
+
+    /// Records the line ranges the client reports as visible for `url`, from a
+    /// `typeServer/visibleRanges` notification.
+    pub(crate) fn set_visible_ranges(&mut self, url: Url, ranges: Vec<Range>) {
+        if ranges.is_empty() {
+            self.visible_ranges.remove(&url);
+        } else {
+            self.visible_ranges.insert(url, ranges);
+        }
+    }
+
+    /// Returns `true` if the client has most recently reported `url` as visible.
+    fn is_visible(&self, url: &Url) -> bool {
+        self.visible_ranges.contains_key(url)
+    }
+
     /// Bumps the revision.
     ///
     /// The revision is used to track when workspace diagnostics may have changed and need to be re-run.
@@ -452,6 +566,14 @@ impl Session {
         // each workspace via the workspace configuration request.
         let mut combined_global_options: Option<GlobalOptions> = None;
 
+        // Registering a workspace and resolving its settings is cheap and mutates
+        // `self.workspaces`, so it happens up front on this thread. Discovering and loading the
+        // project rooted at each workspace is the expensive part (walking the directory tree,
+        // parsing configuration files) and is independent across workspaces, so it's farmed out
+        // to one thread per workspace below. This keeps a multi-root workspace from paying for
+        // project discovery sequentially, root by root.
+        let mut roots = Vec::new();
+
         for (url, options) in workspace_settings {
             tracing::debug!("Initializing workspace `{url}`");
 
@@ -478,9 +600,102 @@ impl Session {
                 continue;
             };
 
-            // For now, create one project database per workspace.
-            // In the future, index the workspace directories to find all projects
-            // and create a project database for each.
+            roots.push((root, workspace.settings_arc()));
+        }
+
+        let index = self.index.as_ref().unwrap().clone();
+        let projects: Vec<(SystemPathBuf, ProjectDatabase)> = std::thread::scope(|scope| {
+            roots
+                .into_iter()
+                .map(|(root, settings)| {
+                    let system = LSPSystem::new(index.clone(), self.native_system.clone());
+                    let client = client.clone();
+                    scope.spawn(move || discover_project(root, &settings, system, &client))
+                })
+                // Collecting into a `Vec` of join handles before the second `.map` is what
+                // makes this parallel: each `spawn` above runs concurrently while we wait for
+                // the next thread's result.
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().expect("project discovery should not panic"))
+                .collect()
+        });
+
+        for (root, db) in projects {
+            // Carry forward diagnostic state if any exists
+            let previous = self.projects.remove(&root);
+            let untracked = previous
+                .map(|state| state.untracked_files_with_pushed_diagnostics)
+                .unwrap_or_default();
+            self.projects.insert(
+                root.clone(),
+                ProjectState {
+                    db,
+                    untracked_files_with_pushed_diagnostics: untracked,
+                },
+            );
+
+            publish_settings_diagnostics(self, client, root);
+        }
+
+        if let Some(global_options) = combined_global_options {
+            let global_settings = global_options.into_settings();
+            if global_settings.diagnostic_mode().is_workspace() {
+                for project in self.projects.values_mut() {
+                    project.db.set_check_mode(CheckMode::AllFiles);
+                }
+            }
+            self.global_settings = Arc::new(global_settings);
+        }
+
+        self.register_capabilities(client);
+
+        assert!(
+            self.workspaces.all_initialized(),
+            "All workspaces should be initialized after calling `initialize_workspaces`"
+        );
+    }
+
+    /// Reloads the settings of already-initialized workspaces, rebuilding their
+    /// [`ProjectDatabase`]s with the freshly resolved settings.
+    ///
+    /// This is used to apply configuration changes received via `workspace/didChangeConfiguration`
+    /// without requiring the client to restart the server. Unlike [`initialize_workspaces`], the
+    /// workspaces are expected to already be initialized.
+    ///
+    /// [`initialize_workspaces`]: Session::initialize_workspaces
+    pub(crate) fn reload_workspaces(
+        &mut self,
+        workspace_settings: Vec<(Url, ClientOptions)>,
+        client: &Client,
+    ) {
+        let mut combined_global_options: Option<GlobalOptions> = None;
+
+        for (url, options) in workspace_settings {
+            tracing::debug!("Reloading settings for workspace `{url}`");
+
+            let ClientOptions {
+                global, workspace, ..
+            } = self
+                .initialization_options
+                .options
+                .clone()
+                .combine(options.clone());
+
+            let unknown_options = &options.unknown;
+            if !unknown_options.is_empty() {
+                warn_about_unknown_options(client, Some(&url), unknown_options);
+            }
+
+            combined_global_options.combine_with(Some(global));
+
+            let workspace_settings = workspace.into_settings();
+            let Some((root, workspace)) = self.workspaces.update_settings(&url, workspace_settings)
+            else {
+                tracing::debug!("Ignoring settings for unknown workspace `{url}`");
+                continue;
+            };
+
             let system = LSPSystem::new(
                 self.index.as_ref().unwrap().clone(),
                 self.native_system.clone(),
@@ -497,37 +712,21 @@ impl Session {
                         metadata.apply_overrides(overrides);
                     }
 
-                    ProjectDatabase::new(metadata, system.clone())
+                    ProjectDatabase::new(metadata, system)
                 });
 
-            let (root, db) = match project {
-                Ok(db) => (root, db),
+            let db = match project {
+                Ok(db) => db,
                 Err(err) => {
                     tracing::error!(
-                        "Failed to create project for `{root}`: {err:#}. \
-                        Falling back to default settings"
+                        "Failed to reload project for `{root}`: {err:#}. Keeping the previous settings"
                     );
 
                     client.show_error_message(format!(
-                        "Failed to load project rooted at {root}. \
+                        "Failed to reload project rooted at {root}. \
                         Please refer to the logs for more details.",
                     ));
-
-                    let db_with_default_settings = ProjectMetadata::from_options(
-                        Options::default(),
-                        root,
-                        None,
-                        MisconfigurationMode::UseDefault,
-                    )
-                    .context("Failed to convert default options to metadata")
-                    .and_then(|metadata| ProjectDatabase::new(metadata, system))
-                    .expect("Default configuration to be valid");
-                    let default_root = db_with_default_settings
-                        .project()
-                        .root(&db_with_default_settings)
-                        .to_path_buf();
-
-                    (default_root, db_with_default_settings)
+                    continue;
                 }
             };
 
@@ -549,20 +748,97 @@ impl Session {
 
         if let Some(global_options) = combined_global_options {
             let global_settings = global_options.into_settings();
-            if global_settings.diagnostic_mode().is_workspace() {
-                for project in self.projects.values_mut() {
-                    project.db.set_check_mode(CheckMode::AllFiles);
-                }
+            let check_mode = if global_settings.diagnostic_mode().is_workspace() {
+                CheckMode::AllFiles
+            } else {
+                CheckMode::OpenFiles
+            };
+            for project in self.projects.values_mut() {
+                project.db.set_check_mode(check_mode);
             }
             self.global_settings = Arc::new(global_settings);
         }
 
         self.register_capabilities(client);
+    }
 
-        assert!(
-            self.workspaces.all_initialized(),
-            "All workspaces should be initialized after calling `initialize_workspaces`"
-        );
+    /// Clears the in-memory caches for every project by rebuilding its [`ProjectDatabase`] from
+    /// scratch, while keeping the already-resolved workspace settings and diagnostic state.
+    pub(crate) fn clear_caches(&mut self, client: &Client) {
+        self.rebuild_project_databases(client, false);
+    }
+
+    /// Restarts the server's project state, rebuilding every [`ProjectDatabase`] from scratch and
+    /// resetting the diagnostic bookkeeping that goes along with it.
+    pub(crate) fn restart(&mut self, client: &Client) {
+        self.rebuild_project_databases(client, true);
+    }
+
+    /// Rebuilds the [`ProjectDatabase`] for every workspace using its already-resolved
+    /// [`WorkspaceSettings`], without requiring a fresh `workspace/configuration` round-trip with
+    /// the client.
+    ///
+    /// If `reset_diagnostics_state` is `true`, the bookkeeping for untracked files with pushed
+    /// diagnostics is cleared rather than carried forward.
+    fn rebuild_project_databases(&mut self, client: &Client, reset_diagnostics_state: bool) {
+        let roots: Vec<SystemPathBuf> = self.projects.keys().cloned().collect();
+
+        for root in roots {
+            let Some(workspace) = self.workspaces.for_path(&root) else {
+                continue;
+            };
+            let settings = workspace.settings_arc();
+
+            let system = LSPSystem::new(
+                self.index.as_ref().unwrap().clone(),
+                self.native_system.clone(),
+            );
+
+            let project = ProjectMetadata::discover(&root, &system)
+                .context("Failed to discover project configuration")
+                .and_then(|mut metadata| {
+                    metadata
+                        .apply_configuration_files(&system)
+                        .context("Failed to apply configuration files")?;
+
+                    if let Some(overrides) = settings.project_options_overrides() {
+                        metadata.apply_overrides(overrides);
+                    }
+
+                    ProjectDatabase::new(metadata, system)
+                });
+
+            let db = match project {
+                Ok(db) => db,
+                Err(err) => {
+                    tracing::error!("Failed to rebuild project for `{root}`: {err:#}");
+
+                    client.show_error_message(format!(
+                        "Failed to reload project rooted at {root}. \
+                        Please refer to the logs for more details.",
+                    ));
+
+                    continue;
+                }
+            };
+
+            let untracked = if reset_diagnostics_state {
+                Vec::new()
+            } else {
+                self.projects.get_mut(&root).map(|state| {
+                    std::mem::take(&mut state.untracked_files_with_pushed_diagnostics)
+                })
+            }
+            .unwrap_or_default();
+
+            self.projects.insert(
+                root,
+                ProjectState {
+                    db,
+                    untracked_files_with_pushed_diagnostics: untracked,
+                },
+            );
+        }
     }
 
     pub(crate) fn take_deferred_messages(&mut self) -> Option<Message> {
@@ -785,6 +1061,8 @@ impl Session {
                 .unwrap_or_else(|| Arc::new(WorkspaceSettings::default())),
             position_encoding: self.position_encoding,
             document: document_handle,
+            semantic_tokens_cache: self.semantic_tokens_cache.clone(),
+            semantic_tokens_result_counter: self.semantic_tokens_result_counter.clone(),
         })
     }
 
@@ -802,6 +1080,16 @@ impl Session {
             position_encoding: self.position_encoding,
             in_test: self.in_test,
             resolved_client_capabilities: self.resolved_client_capabilities,
+            tsp_payload_encoding: self
+                .initialization_options
+                .tsp_payload_encoding
+                .unwrap_or_default(),
+            tsp_enabled: self.initialization_options.tsp_enabled.unwrap_or(true),
+            tsp_supports_container_name: self
+                .initialization_options
+                .tsp_known_result_fields
+                .as_deref()
+                .is_some_and(|fields| fields.iter().any(|field| field == "containerName")),
             revision: self.revision,
         }
     }
@@ -983,6 +1271,8 @@ pub(crate) struct DocumentSnapshot {
     workspace_settings: Arc<WorkspaceSettings>,
     position_encoding: PositionEncoding,
     document: DocumentHandle,
+    semantic_tokens_cache: SemanticTokensCache,
+    semantic_tokens_result_counter: Arc<AtomicU64>,
 }
 
 impl DocumentSnapshot {
@@ -1030,6 +1320,18 @@ impl DocumentSnapshot {
     pub(crate) fn notebook_or_file_path(&self) -> &AnySystemPath {
         self.document.notebook_or_file_path()
     }
+
+    /// Returns the session-wide cache of previously computed semantic tokens, keyed by
+    /// document URL. Used to answer `textDocument/semanticTokens/full/delta` with an edit
+    /// script instead of a full recomputation.
+    pub(crate) fn semantic_tokens_cache(&self) -> &SemanticTokensCache {
+        &self.semantic_tokens_cache
+    }
+
+    /// Returns the counter used to generate unique semantic tokens result ids for this session.
+    pub(crate) fn semantic_tokens_result_counter(&self) -> &Arc<AtomicU64> {
+        &self.semantic_tokens_result_counter
+    }
 }
 
 /// An immutable snapshot of the current state of [`Session`].
@@ -1038,6 +1340,9 @@ pub(crate) struct SessionSnapshot {
     global_settings: Arc<GlobalSettings>,
     position_encoding: PositionEncoding,
     resolved_client_capabilities: ResolvedClientCapabilities,
+    tsp_payload_encoding: tsp::TspPayloadEncoding,
+    tsp_enabled: bool,
+    tsp_supports_container_name: bool,
     in_test: bool,
     revision: u64,
 
@@ -1074,6 +1379,25 @@ impl SessionSnapshot {
         self.resolved_client_capabilities
     }
 
+    /// Returns the encoding negotiated for `typeServer/*` response payloads. See
+    /// [`tsp::TspPayloadEncoding`].
+    pub(crate) fn tsp_payload_encoding(&self) -> tsp::TspPayloadEncoding {
+        self.tsp_payload_encoding
+    }
+
+    /// Returns whether the `typeServer/*` (TSP) facet is enabled for this session. See
+    /// [`crate::session::options::InitializationOptions::tsp_enabled`].
+    pub(crate) fn tsp_enabled(&self) -> bool {
+        self.tsp_enabled
+    }
+
+    /// Returns `true` if the client declared `"containerName"` in `tspKnownResultFields`,
+    /// meaning `typeServer/searchSymbols` matches should be given their enclosing module's name.
+    /// See [`crate::session::options::InitializationOptions::tsp_known_result_fields`].
+    pub(crate) fn tsp_supports_container_name(&self) -> bool {
+        self.tsp_supports_container_name
+    }
+
     pub(crate) const fn in_test(&self) -> bool {
         self.in_test
     }
@@ -1145,6 +1469,27 @@ impl Workspaces {
         }
     }
 
+    /// Updates the settings of an already-initialized workspace, e.g. in response to a
+    /// `workspace/didChangeConfiguration` notification.
+    ///
+    /// ## Returns
+    ///
+    /// `None` if the URL doesn't map to a valid path or if the workspace is not registered.
+    pub(crate) fn update_settings(
+        &mut self,
+        url: &Url,
+        settings: WorkspaceSettings,
+    ) -> Option<(SystemPathBuf, &mut Workspace)> {
+        let path = url.to_file_path().ok()?;
+
+        // Realistically I don't think this can fail because we got the path from a Url
+        let system_path = SystemPathBuf::from_path_buf(path).ok()?;
+
+        let workspace = self.workspaces.get_mut(&system_path)?;
+        workspace.settings = Arc::new(settings);
+        Some((system_path, workspace))
+    }
+
     /// Returns a reference to the workspace for the given path, [`None`] if there's no workspace
     /// registered for the path.
     pub(crate) fn for_path(&self, path: impl AsRef<SystemPath>) -> Option<&Workspace> {
@@ -1577,6 +1922,20 @@ impl DocumentHandle {
             }
         };
 
+        // The cached semantic tokens are only useful for computing the next `/full/delta`
+        // response, which the client will never ask for once the document is closed. Evict them
+        // now rather than leaving them to accumulate for the rest of the session.
+        //
+        // This is eviction-on-close for one specific cache, not a general memory cap with LRU
+        // eviction and a `getServerStats` endpoint - there's no "handle registry" anywhere in
+        // this tree for such a cap to bound. If one gets built later, that's the point to revisit
+        // bounding it this way.
+        session
+            .semantic_tokens_cache
+            .lock()
+            .unwrap()
+            .remove(self.url());
+
         session.bump_revision();
 
         Ok(requires_clear_diagnostics)
@@ -1608,3 +1967,64 @@ pub(super) fn warn_about_unknown_options(
     tracing::warn!("{message}");
     client.show_warning_message(message);
 }
+
+/// Discovers and loads the project rooted at `root`, falling back to default settings and
+/// notifying the client if discovery fails.
+///
+/// This is a free function (rather than a [`Session`] method) so that it can be called from a
+/// worker thread spawned by [`Session::initialize_workspaces`] without needing access to the
+/// session itself.
+fn discover_project(
+    root: SystemPathBuf,
+    settings: &WorkspaceSettings,
+    system: LSPSystem,
+    client: &Client,
+) -> (SystemPathBuf, ProjectDatabase) {
+    // For now, create one project database per workspace.
+    // In the future, index the workspace directories to find all projects
+    // and create a project database for each.
+    let project = ProjectMetadata::discover(&root, &system)
+        .context("Failed to discover project configuration")
+        .and_then(|mut metadata| {
+            metadata
+                .apply_configuration_files(&system)
+                .context("Failed to apply configuration files")?;
+
+            if let Some(overrides) = settings.project_options_overrides() {
+                metadata.apply_overrides(overrides);
+            }
+
+            ProjectDatabase::new(metadata, system.clone())
+        });
+
+    match project {
+        Ok(db) => (root, db),
+        Err(err) => {
+            tracing::error!(
+                "Failed to create project for `{root}`: {err:#}. \
+                Falling back to default settings"
+            );
+
+            client.show_error_message(format!(
+                "Failed to load project rooted at {root}. \
+                Please refer to the logs for more details.",
+            ));
+
+            let db_with_default_settings = ProjectMetadata::from_options(
+                Options::default(),
+                root,
+                None,
+                MisconfigurationMode::UseDefault,
+            )
+            .context("Failed to convert default options to metadata")
+            .and_then(|metadata| ProjectDatabase::new(metadata, system))
+            .expect("Default configuration to be valid");
+            let default_root = db_with_default_settings
+                .project()
+                .root(&db_with_default_settings)
+                .to_path_buf();
+
+            (default_root, db_with_default_settings)
+        }
+    }
+}
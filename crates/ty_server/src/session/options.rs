@@ -43,6 +43,70 @@ pub(crate) struct InitializationOptions {
     /// Tildes (`~`) and environment variables (e.g., `$HOME`) are expanded.
     pub(crate) log_file: Option<SystemPathBuf>,
 
+    /// Shut the server down after this many minutes without receiving any message from the
+    /// client.
+    ///
+    /// This guards against orphaned server processes left behind by a crashed editor, which
+    /// would otherwise linger and keep holding file locks on the project.
+    pub(crate) idle_timeout_minutes: Option<u64>,
+
+    /// Log a warning if handling a single request takes longer than this many seconds.
+    pub(crate) request_timeout_seconds: Option<u64>,
+
+    /// The maximum number of requests that may be pending (received but not yet responded
+    /// to) at once. Further requests are rejected with a "server busy" error until the
+    /// backlog drains, applying backpressure instead of letting an unbounded queue build up
+    /// in memory.
+    pub(crate) max_pending_requests: Option<usize>,
+
+    /// Whether to enable the `typeServer/*` (TSP) facet: `typeServer/searchSymbols` and
+    /// `typeServer/visibleRanges`.
+    ///
+    /// Defaults to `true`. Previously this was implicitly always on, with no way to turn it off
+    /// short of a client simply never sending TSP requests - set this to `false` for clients that
+    /// never want to pay for the TSP request handlers (e.g. a plain LSP client with no type-server
+    /// embedder in the loop), or for server operators who want to disable an unreleased facet.
+    pub(crate) tsp_enabled: Option<bool>,
+
+    /// Number of threads dedicated to handling `typeServer/*` (TSP) traffic.
+    ///
+    /// TSP requests run on their own background pool so that a flood of type-server
+    /// queries from an embedder can't starve regular LSP requests, or vice versa. Defaults
+    /// to a single thread.
+    pub(crate) tsp_worker_threads: Option<std::num::NonZeroUsize>,
+
+    /// Debounce window, in milliseconds, for `publishDiagnostics` notifications.
+    ///
+    /// When set, a burst of `didChange` notifications for the same document collapses into a
+    /// single diagnostics recompute that runs this many milliseconds after the last edit,
+    /// instead of recomputing and republishing diagnostics for every intermediate keystroke.
+    /// This also reduces how often project snapshots churn, which otherwise invalidates any
+    /// `typeServer/*` (TSP) handles an embedder is holding on to mid-edit. Diagnostics are
+    /// published immediately, as before, when this is unset or `0`.
+    pub(crate) diagnostics_debounce_ms: Option<u64>,
+
+    /// The encoding to use for `typeServer/*` (TSP) response payloads that support an
+    /// alternative binary representation.
+    ///
+    /// Defaults to `json`, which embeds the payload directly as JSON, matching every other
+    /// LSP/TSP message. Opting into `messagePack` keeps the outer JSON-RPC envelope (the
+    /// transport doesn't support anything else) but serializes the payload itself with
+    /// MessagePack and carries it as a base64 string, which is cheaper to produce and smaller
+    /// on the wire for large responses such as `typeServer/searchSymbols`.
+    pub(crate) tsp_payload_encoding: Option<crate::session::tsp::TspPayloadEncoding>,
+
+    /// Names of optional `typeServer/*` (TSP) response fields the client declares it
+    /// understands, so the server only spends time computing (and bytes sending) a field once
+    /// something is actually going to look at it.
+    ///
+    /// The only field this currently gates is `"containerName"`, the enclosing module's dotted
+    /// name on each `typeServer/searchSymbols` match. Unset or missing from this list, the field
+    /// is left `None`, exactly as it was before this option existed, so a client that never heard
+    /// of this option keeps getting the same small payload it always did. Unrecognized names are
+    /// ignored rather than rejected, so a client can opt in to a field a newer server understands
+    /// without an older server choking on it, and vice versa.
+    pub(crate) tsp_known_result_fields: Option<Vec<String>>,
+
     /// The remaining options that are dynamic and can change during the runtime of the server.
     #[serde(flatten)]
     pub(crate) options: ClientOptions,
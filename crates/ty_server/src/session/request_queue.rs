@@ -80,6 +80,14 @@ impl Incoming {
         self.pending.contains_key(request_id)
     }
 
+    /// Returns the number of requests that are currently pending.
+    ///
+    /// Used to apply backpressure: see `max_pending_requests` in
+    /// [`InitializationOptions`](crate::session::InitializationOptions).
+    pub(crate) fn len(&self) -> usize {
+        self.pending.len()
+    }
+
     /// Returns the cancellation token for the given request id if the request is still pending.
     pub(crate) fn cancellation_token(
         &self,
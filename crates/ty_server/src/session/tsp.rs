@@ -0,0 +1,109 @@
+//! Custom `typeServer/*` requests and shared payload encoding for the "Type Server Protocol".
+//!
+//! Requests defined here are client-to-server, dispatched the same way as any other request in
+//! `server::api`; see [`SearchSymbols`](crate::SearchSymbols) for the one currently implemented.
+//! The encoding helpers below ([`TspPayloadEncoding`] and friends) exist because that request's
+//! result can get large enough for the encoding to matter.
+
+use serde::{Deserialize, Serialize};
+
+/// The encoding used for a `typeServer/*` response payload that's large enough to care.
+///
+/// Negotiated once via `tspPayloadEncoding` in the initialization options (there's no
+/// per-request negotiation; a client either wants binary payloads or it doesn't). The outer
+/// JSON-RPC envelope is unaffected either way - `lsp-server` only speaks `Content-Length`-framed
+/// JSON, so [`MessagePack`](TspPayloadEncoding::MessagePack) packs the payload with MessagePack
+/// and then carries the resulting bytes as a base64 string rather than replacing the wire format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TspPayloadEncoding {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+/// Below this encoded (pre-compression) size, in bytes, a binary TSP payload is sent as plain
+/// MessagePack rather than gzip-compressed.
+///
+/// Gzip has a fixed header/trailer and per-block framing overhead that can outweigh its savings
+/// on a small payload, and every response already pays for one base64 encode/decode round trip;
+/// a second compression pass isn't worth it until the payload is large enough to amortize that.
+const GZIP_THRESHOLD_BYTES: usize = 8 * 1024;
+
+/// How a [`TspBinaryPayload`] was encoded, so the client knows whether to gunzip before
+/// unpacking the MessagePack bytes.
+///
+/// This is the TSP analogue of an HTTP `Content-Encoding` header: the payload is always
+/// MessagePack underneath, and this only records whether gzip was layered on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TspContentEncoding {
+    MessagePack,
+    MessagePackGzip,
+}
+
+/// A binary TSP response payload, as produced by [`encode_tsp_messagepack`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TspBinaryPayload {
+    pub content_encoding: TspContentEncoding,
+
+    /// Base64 of the MessagePack bytes, gzip-compressed first if `content_encoding` is
+    /// [`TspContentEncoding::MessagePackGzip`].
+    pub data: String,
+}
+
+/// Encodes `value` with MessagePack, gzip-compressing the result first if it's large enough to
+/// be worth it (see [`GZIP_THRESHOLD_BYTES`]), and wraps it as base64.
+///
+/// Returns `None` if MessagePack serialization fails, which the caller should treat as "fall
+/// back to the plain JSON field" rather than failing the request outright - a payload that
+/// round-trips through `serde_json` elsewhere in the same response is expected to also
+/// round-trip through `rmp_serde`, so a failure here means the two encoders disagree about a
+/// type we control, which is a bug worth logging rather than one worth failing a request over.
+/// A gzip failure, by contrast, just falls back to uncompressed MessagePack - there's no reason
+/// compressing an in-memory byte vector should fail, but it isn't worth losing the response over
+/// if it somehow does.
+pub(crate) fn encode_tsp_messagepack<T: Serialize>(value: &T) -> Option<TspBinaryPayload> {
+    use base64::Engine as _;
+
+    let bytes = match rmp_serde::to_vec_named(value) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(
+                "Failed to encode TSP payload as MessagePack, falling back to JSON: {error}"
+            );
+            return None;
+        }
+    };
+
+    if bytes.len() >= GZIP_THRESHOLD_BYTES {
+        if let Some(compressed) = gzip(&bytes) {
+            return Some(TspBinaryPayload {
+                content_encoding: TspContentEncoding::MessagePackGzip,
+                data: base64::engine::general_purpose::STANDARD.encode(compressed),
+            });
+        }
+    }
+
+    Some(TspBinaryPayload {
+        content_encoding: TspContentEncoding::MessagePack,
+        data: base64::engine::general_purpose::STANDARD.encode(bytes),
+    })
+}
+
+/// Gzip-compresses `bytes` at the default compression level, or `None` if the in-memory writer
+/// fails (which, per `flate2`'s own docs, only happens on allocation failure).
+fn gzip(bytes: &[u8]) -> Option<Vec<u8>> {
+    use std::io::Write as _;
+
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(bytes)
+        .and_then(|()| encoder.finish())
+        .inspect_err(|error| tracing::warn!("Failed to gzip-compress TSP payload: {error}"))
+        .ok()
+}
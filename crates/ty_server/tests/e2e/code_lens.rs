@@ -0,0 +1,69 @@
+use crate::TestServerBuilder;
+use lsp_types::request::{CodeLensRequest, CodeLensResolve};
+use lsp_types::{CodeLensParams, PartialResultParams, TextDocumentIdentifier, WorkDoneProgressParams};
+
+#[test]
+fn reference_and_subclass_counts() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        "\
+class Base:
+    pass
+
+
+class Derived(Base):
+    pass
+
+
+def greet():
+    pass
+
+
+greet()
+",
+        1,
+    );
+
+    let lenses_id = server.send_request::<CodeLensRequest>(CodeLensParams {
+        text_document: TextDocumentIdentifier {
+            uri: server.file_uri("foo.py"),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+    let lenses = server
+        .await_response::<CodeLensRequest>(&lenses_id)
+        .expect("Should find code lenses for the class and the function");
+
+    assert_eq!(lenses.len(), 3);
+
+    let titles: Vec<String> = lenses
+        .into_iter()
+        .map(|lens| {
+            let resolve_id = server.send_request::<CodeLensResolve>(lens);
+            let resolved = server
+                .await_response::<CodeLensResolve>(&resolve_id)
+                .expect("server should resolve a code lens it previously returned");
+            resolved
+                .command
+                .expect("Resolved lens should have a command")
+                .title
+        })
+        .collect();
+
+    assert_eq!(
+        titles,
+        vec![
+            "1 subclass".to_string(),
+            "no subclasses".to_string(),
+            "1 reference".to_string(),
+        ]
+    );
+
+    Ok(())
+}
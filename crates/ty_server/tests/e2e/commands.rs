@@ -53,3 +53,61 @@ return 42
 
     Ok(())
 }
+
+#[test]
+fn clear_caches_command() -> Result<()> {
+    let foo = SystemPath::new("foo.py");
+
+    let mut server = TestServerBuilder::new()?
+        .with_file(foo, "x = 1\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let response = execute_command(&mut server, "ty.clearCaches".to_string(), vec![]);
+
+    assert!(response.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn restart_server_command() -> Result<()> {
+    let foo = SystemPath::new("foo.py");
+
+    let mut server = TestServerBuilder::new()?
+        .with_file(foo, "x = 1\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let response = execute_command(&mut server, "ty.restartServer".to_string(), vec![]);
+
+    assert!(response.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn dump_types_for_file_command() -> Result<()> {
+    let foo = SystemPath::new("foo.py");
+
+    let mut server = TestServerBuilder::new()?
+        .with_file(foo, "x = 1\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let uri = server.file_uri(foo);
+    let response = execute_command(
+        &mut server,
+        "ty.dumpTypesForFile".to_string(),
+        vec![serde_json::Value::String(uri.to_string())],
+    );
+
+    let response = response.expect("expect server response");
+    let response = response
+        .as_str()
+        .expect("dump types command to return a string response");
+
+    assert!(response.contains('x'));
+
+    Ok(())
+}
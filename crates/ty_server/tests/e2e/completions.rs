@@ -5,6 +5,8 @@ use ty_server::ClientOptions;
 
 use crate::TestServerBuilder;
 
+static FILTERS: &[(&str, &str)] = &[crate::COMPLETION_RESOLVE_DATA_FILTER];
+
 /// Tests that auto-import is enabled by default.
 #[test]
 fn default_auto_import() -> Result<()> {
@@ -26,31 +28,21 @@ walktr
 
     let completions = server.completion_request(&server.file_uri(foo), Position::new(0, 6));
 
-    insta::assert_json_snapshot!(completions, @r#"
-    [
-      {
-        "label": "walktree (import inspect)",
-        "kind": 3,
-        "sortText": "0",
-        "insertText": "walktree",
-        "additionalTextEdits": [
+    insta::with_settings!({
+        filters => FILTERS.iter().copied(),
+    }, {
+        insta::assert_json_snapshot!(completions, @r#"
+        [
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from inspect import walktree\n"
+            "label": "walktree (import inspect)",
+            "kind": 3,
+            "sortText": "0",
+            "insertText": "walktree",
+            "data": "[DATA]"
           }
         ]
-      }
-    ]
-    "#);
+        "#);
+    });
 
     Ok(())
 }
@@ -89,82 +81,62 @@ walktr
 fn auto_import_shows_qualification() -> Result<()> {
     let workspace_root = SystemPath::new("src");
     let foo = SystemPath::new("src/foo.py");
-    let foo_content = "\
+    let (foo_content, cursor) = crate::cursor_position(
+        "\
 import typing
 
 TypedDi<CURSOR>
-";
+",
+    );
 
     let mut server = TestServerBuilder::new()?
         .with_initialization_options(ClientOptions::default())
         .with_workspace(workspace_root, None)?
-        .with_file(foo, foo_content)?
+        .with_file(foo, &foo_content)?
         .build()
         .wait_until_workspaces_are_initialized();
 
-    server.open_text_document(foo, foo_content, 1);
+    server.open_text_document(foo, &foo_content, 1);
     let _ = server.await_notification::<PublishDiagnostics>();
 
-    let completions = server.completion_request(&server.file_uri(foo), Position::new(2, 7));
-
-    insta::assert_json_snapshot!(completions, @r#"
-    [
-      {
-        "label": "typing.TypedDict",
-        "kind": 6,
-        "sortText": "0",
-        "insertText": "typing.TypedDict"
-      },
-      {
-        "label": "typing.is_typeddict",
-        "kind": 3,
-        "sortText": "1",
-        "insertText": "typing.is_typeddict"
-      },
-      {
-        "label": "_FilterConfigurationTypedDict (import logging.config)",
-        "kind": 7,
-        "sortText": "2",
-        "insertText": "_FilterConfigurationTypedDict",
-        "additionalTextEdits": [
+    let completions = server.completion_request(&server.file_uri(foo), cursor);
+
+    insta::with_settings!({
+        filters => FILTERS.iter().copied(),
+    }, {
+        insta::assert_json_snapshot!(completions, @r#"
+        [
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from logging.config import _FilterConfigurationTypedDict\n"
-          }
-        ]
-      },
-      {
-        "label": "_FormatterConfigurationTypedDict (import logging.config)",
-        "kind": 6,
-        "sortText": "3",
-        "insertText": "_FormatterConfigurationTypedDict",
-        "additionalTextEdits": [
+            "label": "typing.TypedDict",
+            "kind": 6,
+            "sortText": "0",
+            "insertText": "typing.TypedDict",
+            "data": "[DATA]"
+          },
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from logging.config import _FormatterConfigurationTypedDict\n"
+            "label": "typing.is_typeddict",
+            "kind": 3,
+            "sortText": "1",
+            "insertText": "typing.is_typeddict",
+            "data": "[DATA]"
+          },
+          {
+            "label": "_FilterConfigurationTypedDict (import logging.config)",
+            "kind": 7,
+            "sortText": "2",
+            "insertText": "_FilterConfigurationTypedDict",
+            "data": "[DATA]"
+          },
+          {
+            "label": "_FormatterConfigurationTypedDict (import logging.config)",
+            "kind": 6,
+            "sortText": "3",
+            "insertText": "_FormatterConfigurationTypedDict",
+            "data": "[DATA]"
           }
         ]
-      }
-    ]
-    "#);
+        "#);
+    });
 
     Ok(())
 }
@@ -176,110 +148,60 @@ TypedDi<CURSOR>
 fn auto_import_shows_qualification_and_import() -> Result<()> {
     let workspace_root = SystemPath::new("src");
     let foo = SystemPath::new("src/foo.py");
-    let foo_content = "\
+    let (foo_content, cursor) = crate::cursor_position(
+        "\
 TypedDi<CURSOR>
-";
+",
+    );
 
     let mut server = TestServerBuilder::new()?
         .with_initialization_options(ClientOptions::default())
         .with_workspace(workspace_root, None)?
-        .with_file(foo, foo_content)?
+        .with_file(foo, &foo_content)?
         .build()
         .wait_until_workspaces_are_initialized();
 
-    server.open_text_document(foo, foo_content, 1);
+    server.open_text_document(foo, &foo_content, 1);
     let _ = server.await_notification::<PublishDiagnostics>();
 
-    let completions = server.completion_request(&server.file_uri(foo), Position::new(0, 7));
+    let completions = server.completion_request(&server.file_uri(foo), cursor);
 
-    insta::assert_json_snapshot!(completions, @r#"
-    [
-      {
-        "label": "TypedDict (import typing)",
-        "kind": 6,
-        "sortText": "0",
-        "insertText": "TypedDict",
-        "additionalTextEdits": [
+    insta::with_settings!({
+        filters => FILTERS.iter().copied(),
+    }, {
+        insta::assert_json_snapshot!(completions, @r#"
+        [
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from typing import TypedDict\n"
-          }
-        ]
-      },
-      {
-        "label": "is_typeddict (import typing)",
-        "kind": 3,
-        "sortText": "1",
-        "insertText": "is_typeddict",
-        "additionalTextEdits": [
+            "label": "TypedDict (import typing)",
+            "kind": 6,
+            "sortText": "0",
+            "insertText": "TypedDict",
+            "data": "[DATA]"
+          },
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from typing import is_typeddict\n"
-          }
-        ]
-      },
-      {
-        "label": "_FilterConfigurationTypedDict (import logging.config)",
-        "kind": 7,
-        "sortText": "2",
-        "insertText": "_FilterConfigurationTypedDict",
-        "additionalTextEdits": [
+            "label": "is_typeddict (import typing)",
+            "kind": 3,
+            "sortText": "1",
+            "insertText": "is_typeddict",
+            "data": "[DATA]"
+          },
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from logging.config import _FilterConfigurationTypedDict\n"
-          }
-        ]
-      },
-      {
-        "label": "_FormatterConfigurationTypedDict (import logging.config)",
-        "kind": 6,
-        "sortText": "3",
-        "insertText": "_FormatterConfigurationTypedDict",
-        "additionalTextEdits": [
+            "label": "_FilterConfigurationTypedDict (import logging.config)",
+            "kind": 7,
+            "sortText": "2",
+            "insertText": "_FilterConfigurationTypedDict",
+            "data": "[DATA]"
+          },
           {
-            "range": {
-              "start": {
-                "line": 0,
-                "character": 0
-              },
-              "end": {
-                "line": 0,
-                "character": 0
-              }
-            },
-            "newText": "from logging.config import _FormatterConfigurationTypedDict\n"
+            "label": "_FormatterConfigurationTypedDict (import logging.config)",
+            "kind": 6,
+            "sortText": "3",
+            "insertText": "_FormatterConfigurationTypedDict",
+            "data": "[DATA]"
           }
         ]
-      }
-    ]
-    "#);
+        "#);
+    });
 
     Ok(())
 }
@@ -290,14 +212,80 @@ TypedDi<CURSOR>
 fn function_parameter_shows_equals_suffix() -> Result<()> {
     let workspace_root = SystemPath::new("src");
     let foo = SystemPath::new("src/foo.py");
-    let foo_content = "\
+    let (foo_content, cursor) = crate::cursor_position(
+        "\
 import re
 re.match('', '', fla<CURSOR>
-";
+",
+    );
 
     let mut server = TestServerBuilder::new()?
         .with_initialization_options(ClientOptions::default().with_auto_import(false))
         .with_workspace(workspace_root, None)?
+        .with_file(foo, &foo_content)?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(foo, &foo_content, 1);
+    let _ = server.await_notification::<PublishDiagnostics>();
+
+    let completions = server.completion_request(&server.file_uri(foo), cursor);
+
+    insta::with_settings!({
+        filters => FILTERS.iter().copied(),
+    }, {
+        insta::assert_json_snapshot!(completions, @r#"
+        [
+          {
+            "label": "flags=",
+            "kind": 6,
+            "detail": "int",
+            "sortText": "0",
+            "insertText": "flags=",
+            "data": "[DATA]"
+          },
+          {
+            "label": "FloatingPointError",
+            "kind": 7,
+            "detail": "<class 'FloatingPointError'>",
+            "sortText": "1",
+            "data": "[DATA]"
+          },
+          {
+            "label": "PythonFinalizationError",
+            "kind": 7,
+            "detail": "<class 'PythonFinalizationError'>",
+            "sortText": "2",
+            "data": "[DATA]"
+          },
+          {
+            "label": "float",
+            "kind": 7,
+            "detail": "<class 'float'>",
+            "sortText": "3",
+            "data": "[DATA]"
+          }
+        ]
+        "#);
+    });
+
+    Ok(())
+}
+
+/// Tests that `documentation` and `additionalTextEdits` are left unset on the initial
+/// completion list and are only filled in once the client resolves the item via
+/// `completionItem/resolve`.
+#[test]
+fn resolve_fills_in_documentation_and_additional_text_edits() -> Result<()> {
+    let workspace_root = SystemPath::new("src");
+    let foo = SystemPath::new("src/foo.py");
+    let foo_content = "\
+walktr
+";
+
+    let mut server = TestServerBuilder::new()?
+        .with_initialization_options(ClientOptions::default())
+        .with_workspace(workspace_root, None)?
         .with_file(foo, foo_content)?
         .build()
         .wait_until_workspaces_are_initialized();
@@ -305,40 +293,31 @@ re.match('', '', fla<CURSOR>
     server.open_text_document(foo, foo_content, 1);
     let _ = server.await_notification::<PublishDiagnostics>();
 
-    let completions = server.completion_request(&server.file_uri(foo), Position::new(1, 20));
-
-    insta::assert_json_snapshot!(completions, @r#"
-    [
-      {
-        "label": "flags=",
-        "kind": 6,
-        "detail": "int",
-        "sortText": "0",
-        "insertText": "flags="
-      },
-      {
-        "label": "FloatingPointError",
-        "kind": 7,
-        "detail": "<class 'FloatingPointError'>",
-        "documentation": "Floating-point operation failed.\n",
-        "sortText": "1"
-      },
-      {
-        "label": "PythonFinalizationError",
-        "kind": 7,
-        "detail": "<class 'PythonFinalizationError'>",
-        "documentation": "Operation blocked during Python finalization.\n",
-        "sortText": "2"
-      },
-      {
-        "label": "float",
-        "kind": 7,
-        "detail": "<class 'float'>",
-        "documentation": "Convert a string or number to a floating-point number, if possible.\n",
-        "sortText": "3"
-      }
-    ]
-    "#);
+    let mut completions = server.completion_request(&server.file_uri(foo), Position::new(0, 6));
+    let item = completions.remove(0);
+
+    assert_eq!(item.label, "walktree (import inspect)");
+    assert!(item.documentation.is_none());
+    assert!(item.additional_text_edits.is_none());
+    assert!(item.data.is_some());
+
+    let resolved = server.resolve_completion_request(item);
+
+    assert_eq!(resolved.label, "walktree (import inspect)");
+    assert_eq!(
+        resolved.additional_text_edits,
+        Some(vec![lsp_types::TextEdit {
+            range: lsp_types::Range {
+                start: Position::new(0, 0),
+                end: Position::new(0, 0),
+            },
+            new_text: "from inspect import walktree\n".to_string(),
+        }])
+    );
+    assert!(
+        resolved.documentation.is_some(),
+        "resolve should fill in the completion's docstring"
+    );
 
     Ok(())
 }
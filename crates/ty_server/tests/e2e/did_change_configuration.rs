@@ -0,0 +1,82 @@
+use anyhow::Result;
+use lsp_types::request::{RegisterCapability, UnregisterCapability};
+use ruff_db::system::SystemPath;
+use ty_server::{ClientOptions, DiagnosticMode};
+
+use crate::TestServerBuilder;
+
+/// Tests that changing the `ty.diagnosticMode` setting via a `workspace/didChangeConfiguration`
+/// notification takes effect without restarting the server, by re-registering the diagnostic
+/// capability to reflect the new mode.
+#[test]
+fn reload_settings_on_configuration_change() -> Result<()> {
+    let workspace_root = SystemPath::new("foo");
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, Some(ClientOptions::default()))?
+        .enable_diagnostic_dynamic_registration(true)
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    // Consume the diagnostic capability registration sent when the workspace was initialized
+    // with the default (open files) diagnostic mode.
+    let (_, initial_registration) = server.await_request::<RegisterCapability>();
+    assert_eq!(initial_registration.registrations.len(), 1);
+
+    server.set_workspace_configuration(
+        workspace_root,
+        ClientOptions::default().with_diagnostic_mode(DiagnosticMode::Workspace),
+    );
+    server.notify_configuration_changed();
+
+    // The server re-registers the diagnostic capability to reflect the new diagnostic mode,
+    // first unregistering the previous one.
+    let (_, unregistration) = server.await_request::<UnregisterCapability>();
+    assert_eq!(unregistration.unregisterations.len(), 1);
+
+    let (_, params) = server.await_request::<RegisterCapability>();
+    let [registration] = params.registrations.as_slice() else {
+        panic!(
+            "Expected a single registration, got: {:#?}",
+            params.registrations
+        );
+    };
+
+    insta::assert_json_snapshot!(registration, @r#"
+    {
+      "id": "ty/textDocument/diagnostic",
+      "method": "textDocument/diagnostic",
+      "registerOptions": {
+        "documentSelector": null,
+        "identifier": "ty",
+        "interFileDependencies": true,
+        "workDoneProgress": true,
+        "workspaceDiagnostics": true
+      }
+    }
+    "#);
+
+    Ok(())
+}
+
+/// Tests that a `workspace/didChangeConfiguration` notification is ignored (not triggering a
+/// `workspace/configuration` round-trip) when the client doesn't support workspace configuration.
+#[test]
+fn ignored_without_workspace_configuration_support() -> Result<()> {
+    let workspace_root = SystemPath::new("foo");
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, None)?
+        .enable_workspace_configuration(false)
+        .build();
+
+    server.send_notification::<lsp_types::notification::DidChangeConfiguration>(
+        lsp_types::DidChangeConfigurationParams {
+            settings: serde_json::Value::Null,
+        },
+    );
+
+    // Since the client doesn't support workspace configuration, the server has no way to know
+    // which settings changed, so it shouldn't send a `workspace/configuration` request (or
+    // anything else) in response.
+
+    Ok(())
+}
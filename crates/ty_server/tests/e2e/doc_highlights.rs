@@ -0,0 +1,33 @@
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn reads_and_writes() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"x = 1
+x = 2
+print(x)
+"#,
+        1,
+    );
+
+    let highlights = server
+        .document_highlight(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 0,
+                character: 0,
+            },
+        )
+        .expect("Can highlight occurrences of `x`");
+
+    assert_json_snapshot!(highlights);
+
+    Ok(())
+}
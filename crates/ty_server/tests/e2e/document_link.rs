@@ -0,0 +1,63 @@
+use crate::TestServerBuilder;
+use lsp_types::request::DocumentLinkRequest;
+use lsp_types::{
+    DocumentLinkParams, PartialResultParams, TextDocumentIdentifier, WorkDoneProgressParams,
+};
+
+#[test]
+fn links_for_import_statements() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        "\
+import os
+from os import path
+",
+        1,
+    );
+
+    let request_id = server.send_request::<DocumentLinkRequest>(DocumentLinkParams {
+        text_document: TextDocumentIdentifier {
+            uri: server.file_uri("foo.py"),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let links = server
+        .await_response::<DocumentLinkRequest>(&request_id)
+        .expect("should find document links for both import statements");
+
+    assert_eq!(links.len(), 2);
+    assert!(links.iter().all(|link| link.target.is_some()));
+
+    Ok(())
+}
+
+#[test]
+fn no_links_without_imports() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "x = 1\n", 1);
+
+    let request_id = server.send_request::<DocumentLinkRequest>(DocumentLinkParams {
+        text_document: TextDocumentIdentifier {
+            uri: server.file_uri("foo.py"),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let links = server.await_response::<DocumentLinkRequest>(&request_id);
+
+    assert!(links.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use lsp_types::{ClientCapabilities, DocumentSymbolClientCapabilities, TextDocumentClientCapabilities};
+
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn flat_by_default() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"class Outer:
+    def method(self):
+        pass
+
+    class Inner:
+        def inner_method(self):
+            pass
+"#,
+        1,
+    );
+
+    let symbols = server
+        .document_symbol(&server.file_uri("foo.py"))
+        .expect("Can list document symbols");
+
+    assert_json_snapshot!(symbols);
+
+    Ok(())
+}
+
+#[test]
+fn nested_with_hierarchical_client_support() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_client_capabilities(ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                document_symbol: Some(DocumentSymbolClientCapabilities {
+                    hierarchical_document_symbol_support: Some(true),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"class Outer:
+    def method(self):
+        pass
+
+    class Inner:
+        def inner_method(self):
+            pass
+"#,
+        1,
+    );
+
+    let symbols = server
+        .document_symbol(&server.file_uri("foo.py"))
+        .expect("Can list document symbols");
+
+    assert_json_snapshot!(symbols);
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn local_binding() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def test(): ...
+
+test()
+"#,
+        1,
+    );
+
+    let response = server
+        .goto_definition(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 2,
+                character: 0,
+            },
+        )
+        .expect("Can resolve the definition of `test`");
+
+    assert_json_snapshot!(response);
+
+    Ok(())
+}
+
+#[test]
+fn cross_file_import() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("lib.py", "def helper(): ...\n")?
+        .with_file("main.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "main.py",
+        r#"from lib import helper
+
+helper()
+"#,
+        1,
+    );
+
+    let response = server
+        .goto_definition(
+            &server.file_uri("main.py"),
+            lsp_types::Position {
+                line: 2,
+                character: 0,
+            },
+        )
+        .expect("Can resolve `helper` across files");
+
+    assert_json_snapshot!(response);
+
+    Ok(())
+}
+
+#[test]
+fn attribute_target() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"class Point:
+    def __init__(self):
+        self.x = 1
+
+p = Point()
+p.x
+"#,
+        1,
+    );
+
+    let response = server
+        .goto_definition(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 5,
+                character: 2,
+            },
+        )
+        .expect("Can resolve the `x` attribute target");
+
+    assert_json_snapshot!(response);
+
+    Ok(())
+}
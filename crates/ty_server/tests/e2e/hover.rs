@@ -0,0 +1,60 @@
+use crate::TestServerBuilder;
+use lsp_types::{
+    ClientCapabilities, HoverClientCapabilities, MarkupKind, Position, TextDocumentClientCapabilities,
+};
+
+#[test]
+fn plaintext_by_default() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "def test(): ...\n", 1);
+
+    let hover = server
+        .hover_request("foo.py", Position::new(0, 5))
+        .expect("Can hover over `test`");
+
+    let lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("Expected markup hover contents, got: {:?}", hover.contents);
+    };
+
+    assert_eq!(markup.kind, MarkupKind::PlainText);
+
+    Ok(())
+}
+
+#[test]
+fn markdown_when_supported() -> anyhow::Result<()> {
+    let client_capabilities = ClientCapabilities {
+        text_document: Some(TextDocumentClientCapabilities {
+            hover: Some(HoverClientCapabilities {
+                content_format: Some(vec![MarkupKind::Markdown]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut server = TestServerBuilder::new()?
+        .with_client_capabilities(client_capabilities)
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "def test(): ...\n", 1);
+
+    let hover = server
+        .hover_request("foo.py", Position::new(0, 5))
+        .expect("Can hover over `test`");
+
+    let lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("Expected markup hover contents, got: {:?}", hover.contents);
+    };
+
+    assert_eq!(markup.kind, MarkupKind::Markdown);
+
+    Ok(())
+}
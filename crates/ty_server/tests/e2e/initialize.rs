@@ -78,9 +78,46 @@ fn workspace_diagnostic_registration_without_configuration() -> Result<()> {
     Ok(())
 }
 
-/// Tests that the server sends a registration request for diagnostics if open files diagnostics
-/// are enabled via initialization options and dynamic registration is enabled, even if the
-/// workspace configuration is not supported by the client.
+/// Tests that the server sends a registration request for the file watcher, with a single
+/// catch-all `**` glob, when the client doesn't support relative file watcher patterns.
+#[test]
+fn file_watcher_registration_without_relative_pattern_support() -> Result<()> {
+    let workspace_root = SystemPath::new("foo");
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, None)?
+        .enable_did_change_watched_files(true, false)
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let (_, params) = server.await_request::<RegisterCapability>();
+    let [registration] = params.registrations.as_slice() else {
+        panic!(
+            "Expected a single registration, got: {:#?}",
+            params.registrations
+        );
+    };
+
+    insta::assert_json_snapshot!(registration, @r#"
+    {
+      "id": "ty/workspace/didChangeWatchedFiles",
+      "method": "workspace/didChangeWatchedFiles",
+      "registerOptions": {
+        "watchers": [
+          {
+            "globPattern": "**",
+            "kind": 7
+          }
+        ]
+      }
+    }
+    "#);
+
+    Ok(())
+}
+
+/// Tests that the server sends a registration request for open files diagnostics if open files
+/// diagnostics are enabled via initialization options and dynamic registration is enabled, even
+/// if the workspace configuration is not supported by the client.
 #[test]
 fn open_files_diagnostic_registration_without_configuration() -> Result<()> {
     let workspace_root = SystemPath::new("foo");
@@ -0,0 +1,62 @@
+use crate::TestServerBuilder;
+use lsp_types::request::LinkedEditingRangeRequest;
+use lsp_types::{
+    LinkedEditingRangeParams, PartialResultParams, Position, TextDocumentIdentifier,
+    TextDocumentPositionParams, WorkDoneProgressParams,
+};
+
+#[test]
+fn linked_editing_range_for_local_variable() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "def f():\n    value = 1\n    return value\n", 1);
+
+    let request_id = server.send_request::<LinkedEditingRangeRequest>(LinkedEditingRangeParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: server.file_uri("foo.py"),
+            },
+            position: Position::new(1, 5),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let ranges = server
+        .await_response::<LinkedEditingRangeRequest>(&request_id)
+        .expect("should find linked editing ranges for the local variable");
+
+    assert_eq!(ranges.ranges.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn no_linked_editing_range_for_literal() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "x = 1\n", 1);
+
+    let request_id = server.send_request::<LinkedEditingRangeRequest>(LinkedEditingRangeParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: server.file_uri("foo.py"),
+            },
+            position: Position::new(0, 4),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let ranges = server.await_response::<LinkedEditingRangeRequest>(&request_id);
+
+    assert!(ranges.is_none());
+
+    Ok(())
+}
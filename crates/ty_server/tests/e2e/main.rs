@@ -28,16 +28,33 @@
 //! [`await_notification`]: TestServer::await_notification
 
 mod code_actions;
+mod code_lens;
 mod commands;
 mod completions;
+mod did_change_configuration;
+mod doc_highlights;
+mod document_link;
+mod document_symbols;
+mod goto_definition;
+mod hover;
 mod initialize;
 mod inlay_hints;
+mod linked_editing_range;
+mod moniker;
+mod multi_root_workspaces;
 mod notebook;
+mod prepare_rename;
 mod publish_diagnostics;
 mod pull_diagnostics;
+mod references;
 mod rename;
+mod search_symbols;
+mod selection_range;
 mod semantic_tokens;
+mod server_builder;
 mod signature_help;
+mod visible_ranges;
+mod will_rename_files;
 
 use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::num::NonZeroUsize;
@@ -55,20 +72,23 @@ use lsp_types::notification::{
     Initialized, Notification,
 };
 use lsp_types::request::{
-    Completion, DocumentDiagnosticRequest, HoverRequest, Initialize, InlayHintRequest,
-    PrepareRenameRequest, Request, Shutdown, SignatureHelpRequest, WorkspaceConfiguration,
-    WorkspaceDiagnosticRequest,
+    Completion, DocumentDiagnosticRequest, DocumentHighlightRequest, DocumentSymbolRequest,
+    HoverRequest, Initialize, InlayHintRequest, PrepareRenameRequest, Request, SelectionRangeRequest,
+    Shutdown, SignatureHelpRequest, WorkspaceConfiguration, WorkspaceDiagnosticRequest,
 };
 use lsp_types::{
     ClientCapabilities, CompletionItem, CompletionParams, CompletionResponse,
     CompletionTriggerKind, ConfigurationParams, DiagnosticClientCapabilities,
     DidChangeTextDocumentParams, DidChangeWatchedFilesClientCapabilities,
     DidChangeWatchedFilesParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
-    DocumentDiagnosticParams, DocumentDiagnosticReportResult, FileEvent, Hover, HoverParams,
-    InitializeParams, InitializeResult, InitializedParams, InlayHint, InlayHintClientCapabilities,
+    DocumentDiagnosticParams, DocumentDiagnosticReportResult, DocumentSymbolClientCapabilities,
+    DocumentSymbolParams, DocumentSymbolResponse, FileEvent, GotoDefinitionParams,
+    Hover, HoverParams, InitializeParams, InitializeResult, InitializedParams, InlayHint,
+    InlayHintClientCapabilities,
     InlayHintParams, NumberOrString, PartialResultParams, Position, PreviousResultId,
-    PublishDiagnosticsClientCapabilities, Range, SemanticTokensResult, SignatureHelp,
-    SignatureHelpParams, SignatureHelpTriggerKind, TextDocumentClientCapabilities,
+    PublishDiagnosticsClientCapabilities, Range, ReferenceParams, SelectionRange,
+    SelectionRangeParams, SemanticTokensResult,
+    SignatureHelp, SignatureHelpParams, SignatureHelpTriggerKind, TextDocumentClientCapabilities,
     TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem,
     TextDocumentPositionParams, Url, VersionedTextDocumentIdentifier, WorkDoneProgressParams,
     WorkspaceClientCapabilities, WorkspaceDiagnosticParams, WorkspaceDiagnosticReportResult,
@@ -77,7 +97,10 @@ use lsp_types::{
 use ruff_db::system::{OsSystem, SystemPath, SystemPathBuf, TestSystem};
 use rustc_hash::FxHashMap;
 use tempfile::TempDir;
-use ty_server::{ClientOptions, LogLevel, Server, init_logging};
+use ty_server::{
+    ClientOptions, LogLevel, SearchSymbols, SearchSymbolsParams, SearchSymbolsResult,
+    ServerBuilder, ServerHandle, VisibleRanges, VisibleRangesParams, init_logging, middleware,
+};
 
 /// Number of times to retry receiving a message before giving up
 const RETRY_COUNT: usize = 5;
@@ -202,6 +225,33 @@ pub(crate) struct TestServer {
     /// Whether a Shutdown request has been sent by the test
     /// and the exit sequence should be skipped during `Drop`
     shutdown_requested: bool,
+
+    /// The method name of each server-issued request that's still awaiting a response,
+    /// keyed by request ID. Used to look up the [`FaultOverride`] to apply when the test
+    /// responds to that request.
+    pending_request_methods: HashMap<RequestId, String>,
+
+    /// Per-method fault injection overrides, applied when the test responds to a
+    /// server-issued request for that method. See [`TestServer::with_fault`].
+    fault_overrides: HashMap<String, FaultOverride>,
+
+    /// A handle for interacting with the running server from outside the main loop, e.g. to
+    /// grow its background worker pool via [`ServerHandle::set_background_worker_threads`].
+    ///
+    /// Only `None` while [`TestServer::new`] is still performing the initialization handshake.
+    handle: Option<ServerHandle>,
+}
+
+/// A fault to inject when the test client responds to a server-issued request, so that
+/// client-side retry and stale-handle logic can be exercised against the real server loop.
+#[derive(Debug, Clone)]
+pub(crate) enum FaultOverride {
+    /// Sleep for `delay` before sending the response.
+    Delay(Duration),
+    /// Respond with an error instead of the result the test provided.
+    Error { code: i32, message: String },
+    /// Don't send a response at all.
+    Drop,
 }
 
 impl TestServer {
@@ -211,7 +261,9 @@ impl TestServer {
         test_context: TestContext,
         capabilities: ClientCapabilities,
         initialization_options: Option<ClientOptions>,
+        extra_initialization_options: serde_json::Map<String, serde_json::Value>,
         env_vars: Vec<(String, String)>,
+        middleware: Vec<Box<dyn middleware::TspMiddleware>>,
     ) -> Self {
         setup_tracing();
 
@@ -228,13 +280,27 @@ impl TestServer {
             test_system.set_env_var(name, value);
         }
 
+        // `ServerBuilder::build` performs the LSP initialization handshake synchronously, which
+        // has to interleave with this constructor sending the client side of that handshake
+        // below, so both have to run on the server thread. Send the handle back over this
+        // channel once the handshake completes, before the thread blocks in `Server::run`.
+        let (handle_sender, handle_receiver) = crossbeam::channel::bounded(1);
+
         // Start the server in a separate thread
         let server_thread = std::thread::spawn(move || {
             // TODO: This should probably be configurable to test concurrency issues
             let worker_threads = NonZeroUsize::new(1).unwrap();
 
-            match Server::new(worker_threads, server_connection, test_system, true) {
+            let mut builder = ServerBuilder::new(server_connection, test_system)
+                .worker_threads(worker_threads)
+                .in_test(true);
+            for middleware in middleware {
+                builder = builder.with_middleware(middleware);
+            }
+
+            match builder.build() {
                 Ok(server) => {
+                    handle_sender.send(server.handle()).ok();
                     if let Err(err) = server.run() {
                         panic!("Server stopped with error: {err:?}");
                     }
@@ -255,7 +321,7 @@ impl TestServer {
             .filter_map(|(folder, options)| Some((folder.uri, options?)))
             .collect::<HashMap<_, _>>();
 
-        Self {
+        let mut server = Self {
             server_thread: Some(server_thread),
             client_connection: Some(client_connection),
             test_context,
@@ -266,8 +332,38 @@ impl TestServer {
             initialize_response: None,
             workspace_configurations,
             shutdown_requested: false,
+            pending_request_methods: HashMap::new(),
+            fault_overrides: HashMap::new(),
+            handle: None,
         }
-        .initialize(workspace_folders, capabilities, initialization_options)
+        .initialize(
+            workspace_folders,
+            capabilities,
+            initialization_options,
+            extra_initialization_options,
+        );
+
+        server.handle = Some(
+            handle_receiver
+                .recv()
+                .expect("server thread should send its handle before running"),
+        );
+        server
+    }
+
+    /// Returns a handle for interacting with the running server from outside the main loop.
+    pub(crate) fn handle(&self) -> &ServerHandle {
+        self.handle
+            .as_ref()
+            .expect("the handle is set before `TestServer::new` returns")
+    }
+
+    /// Registers a [`FaultOverride`] for `method`, consumed and applied the next time the
+    /// test responds to a server-issued request for that method.
+    #[expect(dead_code)]
+    pub(crate) fn with_fault(mut self, method: impl Into<String>, fault: FaultOverride) -> Self {
+        self.fault_overrides.insert(method.into(), fault);
+        self
     }
 
     /// Perform LSP initialization handshake
@@ -280,15 +376,35 @@ impl TestServer {
         workspace_folders: Vec<WorkspaceFolder>,
         capabilities: ClientCapabilities,
         initialization_options: Option<ClientOptions>,
+        extra_initialization_options: serde_json::Map<String, serde_json::Value>,
     ) -> Self {
+        let initialization_options = if initialization_options.is_none()
+            && extra_initialization_options.is_empty()
+        {
+            None
+        } else {
+            let mut value = initialization_options
+                .map(|options| {
+                    serde_json::to_value(options)
+                        .context("Failed to serialize initialization options to `ClientOptions`")
+                        .unwrap()
+                })
+                .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+
+            // `InitializationOptions` flattens `ClientOptions` together with server-startup-only
+            // fields (e.g. `tspPayloadEncoding`) that this test harness has no typed setter for;
+            // merge them in as raw JSON instead of widening `ClientOptions`'s public shape.
+            if let serde_json::Value::Object(map) = &mut value {
+                map.extend(extra_initialization_options);
+            }
+
+            Some(value)
+        };
+
         let init_params = InitializeParams {
             capabilities,
             workspace_folders: Some(workspace_folders),
-            initialization_options: initialization_options.map(|options| {
-                serde_json::to_value(options)
-                    .context("Failed to serialize initialization options to `ClientOptions`")
-                    .unwrap()
-            }),
+            initialization_options,
             ..Default::default()
         };
 
@@ -364,6 +480,11 @@ impl TestServer {
     /// exited unexpectedly or panicked.
     #[track_caller]
     fn send(&mut self, message: Message) {
+        let message = match self.apply_fault_override(message) {
+            Some(message) => message,
+            None => return,
+        };
+
         if self
             .client_connection
             .as_ref()
@@ -376,6 +497,36 @@ impl TestServer {
         }
     }
 
+    /// Applies a registered [`FaultOverride`] to an outgoing response, if one was registered
+    /// for the method of the server request it answers.
+    ///
+    /// Returns `None` if the message should be dropped instead of sent.
+    fn apply_fault_override(&mut self, message: Message) -> Option<Message> {
+        let Message::Response(response) = &message else {
+            return Some(message);
+        };
+
+        let method = self.pending_request_methods.remove(&response.id)?;
+        let fault = self.fault_overrides.remove(&method)?;
+
+        match fault {
+            FaultOverride::Delay(delay) => {
+                std::thread::sleep(delay);
+                Some(message)
+            }
+            FaultOverride::Error { code, message: msg } => Some(Message::Response(Response {
+                id: response.id.clone(),
+                result: None,
+                error: Some(ResponseError {
+                    code,
+                    message: msg,
+                    data: None,
+                }),
+            })),
+            FaultOverride::Drop => None,
+        }
+    }
+
     /// Send a request to the server and return the request ID.
     ///
     /// The caller can use this ID to later retrieve the response using [`await_response`].
@@ -594,6 +745,18 @@ impl TestServer {
             .unwrap_or_else(|err| panic!("Failed to receive server request `{}`: {err}", R::METHOD))
     }
 
+    /// Send a successful response of type `R` back to the server for a request previously
+    /// received via [`await_request`] or [`try_await_request`].
+    ///
+    /// This is mainly useful for server-issued requests that don't have dedicated handling in
+    /// this harness yet.
+    ///
+    /// [`await_request`]: TestServer::await_request
+    /// [`try_await_request`]: TestServer::try_await_request
+    pub(crate) fn respond_to_request<R: Request>(&mut self, id: RequestId, result: R::Result) {
+        self.send(Message::Response(Response::new_ok(id, result)));
+    }
+
     /// Wait for a request of the specified type from the server and return the request ID and
     /// parameters.
     ///
@@ -664,6 +827,8 @@ impl TestServer {
         match message {
             Message::Request(request) => {
                 tracing::debug!("Received server request `{}`", &request.method);
+                self.pending_request_methods
+                    .insert(request.id.clone(), request.method.clone());
                 self.requests.push_back(request);
             }
             Message::Response(response) => {
@@ -705,6 +870,37 @@ impl TestServer {
         });
     }
 
+    /// Updates the configuration the test client will respond with for `workspace_root` the next
+    /// time the server sends a `workspace/configuration` request, e.g. in response to sending a
+    /// `workspace/didChangeConfiguration` notification via [`notify_configuration_changed`].
+    ///
+    /// [`notify_configuration_changed`]: TestServer::notify_configuration_changed
+    pub(crate) fn set_workspace_configuration(
+        &mut self,
+        workspace_root: impl AsRef<SystemPath>,
+        options: ClientOptions,
+    ) {
+        self.workspace_configurations
+            .insert(self.file_uri(workspace_root), options);
+    }
+
+    /// Sends a `workspace/didChangeConfiguration` notification and handles the resulting
+    /// `workspace/configuration` request using the configurations set via
+    /// [`set_workspace_configuration`].
+    ///
+    /// [`set_workspace_configuration`]: TestServer::set_workspace_configuration
+    #[track_caller]
+    pub(crate) fn notify_configuration_changed(&mut self) {
+        self.send_notification::<lsp_types::notification::DidChangeConfiguration>(
+            lsp_types::DidChangeConfigurationParams {
+                settings: serde_json::Value::Null,
+            },
+        );
+
+        let (request_id, params) = self.await_request::<WorkspaceConfiguration>();
+        self.handle_workspace_configuration_request(request_id, &params);
+    }
+
     /// Handle workspace configuration requests from the server.
     ///
     /// Use the [`get_request`] method to wait for the server to send this request.
@@ -822,21 +1018,27 @@ impl TestServer {
         self.send_notification::<DidChangeWatchedFiles>(params);
     }
 
+    /// Send a `textDocument/prepareRename` request for the given document position.
+    pub(crate) fn prepare_rename(
+        &mut self,
+        document: &Url,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::PrepareRenameResponse> {
+        self.send_request_await::<PrepareRenameRequest>(lsp_types::TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: document.clone(),
+            },
+            position,
+        })
+    }
+
     pub(crate) fn rename(
         &mut self,
         document: &Url,
         position: lsp_types::Position,
         new_name: &str,
     ) -> Result<Option<WorkspaceEdit>, ()> {
-        if self
-            .send_request_await::<PrepareRenameRequest>(lsp_types::TextDocumentPositionParams {
-                text_document: TextDocumentIdentifier {
-                    uri: document.clone(),
-                },
-                position,
-            })
-            .is_none()
-        {
+        if self.prepare_rename(document, position).is_none() {
             return Err(());
         }
 
@@ -854,6 +1056,93 @@ impl TestServer {
         )
     }
 
+    /// Send a `textDocument/definition` request for the given document position.
+    pub(crate) fn goto_definition(
+        &mut self,
+        document: &Url,
+        position: lsp_types::Position,
+    ) -> Option<lsp_types::GotoDefinitionResponse> {
+        self.send_request_await::<lsp_types::request::GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: document.clone(),
+                },
+                position,
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+    }
+
+    /// Send a `textDocument/documentHighlight` request for the given document position.
+    pub(crate) fn document_highlight(
+        &mut self,
+        document: &Url,
+        position: lsp_types::Position,
+    ) -> Option<Vec<lsp_types::DocumentHighlight>> {
+        self.send_request_await::<DocumentHighlightRequest>(
+            lsp_types::DocumentHighlightParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: document.clone(),
+                    },
+                    position,
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+    }
+
+    /// Send a `textDocument/selectionRange` request for the given document positions.
+    pub(crate) fn selection_range(
+        &mut self,
+        document: &Url,
+        positions: Vec<lsp_types::Position>,
+    ) -> Option<Vec<SelectionRange>> {
+        self.send_request_await::<SelectionRangeRequest>(SelectionRangeParams {
+            text_document: TextDocumentIdentifier {
+                uri: document.clone(),
+            },
+            positions,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+    }
+
+    /// Send a `textDocument/documentSymbol` request for the given document.
+    pub(crate) fn document_symbol(&mut self, document: &Url) -> Option<DocumentSymbolResponse> {
+        self.send_request_await::<DocumentSymbolRequest>(DocumentSymbolParams {
+            text_document: TextDocumentIdentifier {
+                uri: document.clone(),
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+    }
+
+    /// Send a `textDocument/references` request for the given document position.
+    pub(crate) fn references(
+        &mut self,
+        document: &Url,
+        position: lsp_types::Position,
+        include_declaration: bool,
+    ) -> Option<Vec<lsp_types::Location>> {
+        self.send_request_await::<lsp_types::request::References>(ReferenceParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier {
+                    uri: document.clone(),
+                },
+                position,
+            },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: lsp_types::ReferenceContext {
+                include_declaration,
+            },
+        })
+    }
+
     /// Send a `textDocument/diagnostic` request for the document at the given path.
     pub(crate) fn document_diagnostic_request(
         &mut self,
@@ -896,10 +1185,21 @@ impl TestServer {
         path: impl AsRef<SystemPath>,
         position: Position,
     ) -> Option<Hover> {
+        let uri = self.file_uri(path);
+        self.hover(&uri, position)
+    }
+
+    /// Send a `textDocument/hover` request for the given document and position.
+    ///
+    /// Unlike [`hover_request`], this takes the document's URI directly, which is required for
+    /// documents that aren't backed by a file on disk, such as notebook cells.
+    ///
+    /// [`hover_request`]: TestServer::hover_request
+    pub(crate) fn hover(&mut self, document: &Url, position: Position) -> Option<Hover> {
         let params = HoverParams {
             text_document_position_params: TextDocumentPositionParams {
                 text_document: TextDocumentIdentifier {
-                    uri: self.file_uri(path),
+                    uri: document.clone(),
                 },
                 position,
             },
@@ -951,6 +1251,14 @@ impl TestServer {
         }
     }
 
+    /// Sends a `completionItem/resolve` request for a [`CompletionItem`] previously returned by
+    /// [`Self::completion_request`].
+    pub(crate) fn resolve_completion_request(&mut self, item: CompletionItem) -> CompletionItem {
+        let id = self.send_request::<lsp_types::request::ResolveCompletionItem>(item);
+        self.await_response::<lsp_types::request::ResolveCompletionItem>(&id)
+            .expect("server should resolve a completion item it previously returned")
+    }
+
     /// Sends a `textDocument/signatureHelp` request for the document at the given URL and position.
     pub(crate) fn signature_help_request(
         &mut self,
@@ -985,6 +1293,41 @@ impl TestServer {
             },
         )
     }
+
+    pub(crate) fn semantic_tokens_full_delta_request(
+        &mut self,
+        uri: &Url,
+        previous_result_id: &str,
+    ) -> Option<lsp_types::SemanticTokensFullDeltaResult> {
+        self.send_request_await::<lsp_types::request::SemanticTokensFullDeltaRequest>(
+            lsp_types::SemanticTokensDeltaParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                previous_result_id: previous_result_id.to_string(),
+                work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+                partial_result_params: PartialResultParams::default(),
+            },
+        )
+    }
+
+    /// Send a `typeServer/searchSymbols` request with the given query and optional timeout.
+    pub(crate) fn search_symbols_request(
+        &mut self,
+        query: impl Into<String>,
+        timeout_ms: Option<u64>,
+    ) -> SearchSymbolsResult {
+        self.send_request_await::<SearchSymbols>(SearchSymbolsParams {
+            query: query.into(),
+            timeout_ms,
+        })
+    }
+
+    /// Send a `typeServer/visibleRanges` notification for the given document.
+    pub(crate) fn notify_visible_ranges(&mut self, document: &Url, ranges: Vec<Range>) {
+        self.send_notification::<VisibleRanges>(VisibleRangesParams {
+            uri: document.clone(),
+            ranges,
+        });
+    }
 }
 
 impl fmt::Debug for TestServer {
@@ -1072,8 +1415,12 @@ pub(crate) struct TestServerBuilder {
     test_context: TestContext,
     workspaces: Vec<(WorkspaceFolder, Option<ClientOptions>)>,
     initialization_options: Option<ClientOptions>,
+    extra_initialization_options: serde_json::Map<String, serde_json::Value>,
     client_capabilities: ClientCapabilities,
     env_vars: Vec<(String, String)>,
+    python_version: Option<String>,
+    venv: Option<SystemPathBuf>,
+    middleware: Vec<Box<dyn middleware::TspMiddleware>>,
 }
 
 impl TestServerBuilder {
@@ -1103,8 +1450,12 @@ impl TestServerBuilder {
             workspaces: Vec::new(),
             test_context: TestContext::new()?,
             initialization_options: None,
+            extra_initialization_options: serde_json::Map::new(),
             client_capabilities,
             env_vars: Vec::new(),
+            python_version: None,
+            venv: None,
+            middleware: Vec::new(),
         })
     }
 
@@ -1114,6 +1465,27 @@ impl TestServerBuilder {
         self
     }
 
+    /// Sets a top-level initialization option that isn't exposed by [`ClientOptions`], such as
+    /// `tspPayloadEncoding` or `tspKnownResultFields`, which only take effect at server startup.
+    pub(crate) fn with_raw_initialization_option(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<serde_json::Value>,
+    ) -> Self {
+        self.extra_initialization_options
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Registers a [`middleware::TspMiddleware`] hook with the server under test.
+    pub(crate) fn with_middleware(
+        mut self,
+        middleware: Box<dyn middleware::TspMiddleware>,
+    ) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
     /// Set an environment variable for the test server's system.
     pub(crate) fn with_env_var(
         mut self,
@@ -1136,11 +1508,6 @@ impl TestServerBuilder {
         workspace_root: &SystemPath,
         options: Option<ClientOptions>,
     ) -> Result<Self> {
-        // TODO: Support multiple workspaces in the test server
-        if self.workspaces.len() == 1 {
-            anyhow::bail!("Test server doesn't support multiple workspaces yet");
-        }
-
         let workspace_path = self.test_context.root().join(workspace_root);
         fs::create_dir_all(workspace_path.as_std_path())?;
 
@@ -1203,14 +1570,21 @@ impl TestServerBuilder {
         self
     }
 
-    /// Enable or disable file watching capability
-    #[expect(dead_code)]
-    pub(crate) fn enable_did_change_watched_files(mut self, enabled: bool) -> Self {
+    /// Enable or disable dynamic registration of the file watcher capability, optionally with
+    /// support for relative file watcher patterns.
+    pub(crate) fn enable_did_change_watched_files(
+        mut self,
+        enabled: bool,
+        relative_pattern_support: bool,
+    ) -> Self {
         self.client_capabilities
             .workspace
             .get_or_insert_default()
             .did_change_watched_files = if enabled {
-            Some(DidChangeWatchedFilesClientCapabilities::default())
+            Some(DidChangeWatchedFilesClientCapabilities {
+                dynamic_registration: Some(true),
+                relative_pattern_support: Some(relative_pattern_support),
+            })
         } else {
             None
         };
@@ -1238,12 +1612,44 @@ impl TestServerBuilder {
     }
 
     /// Set custom client capabilities (overrides any previously set capabilities)
-    #[expect(dead_code)]
     pub(crate) fn with_client_capabilities(mut self, capabilities: ClientCapabilities) -> Self {
         self.client_capabilities = capabilities;
         self
     }
 
+    /// Pin the Python version the test project is resolved against, equivalent to setting
+    /// `environment.python-version` in `ty.toml`.
+    pub(crate) fn with_python_version(mut self, version: impl Into<String>) -> Result<Self> {
+        self.python_version = Some(version.into());
+        self.write_environment_options()
+    }
+
+    /// Point the test project at a Python environment, equivalent to setting
+    /// `environment.python` in `ty.toml`.
+    pub(crate) fn with_venv(mut self, path: impl AsRef<SystemPath>) -> Result<Self> {
+        self.venv = Some(path.as_ref().to_path_buf());
+        self.write_environment_options()
+    }
+
+    /// Rewrites `ty.toml`'s `[environment]` section from the state accumulated by
+    /// [`with_python_version`](Self::with_python_version) and [`with_venv`](Self::with_venv).
+    ///
+    /// This overwrites any `ty.toml` written by a prior call to either of those methods (or to
+    /// [`with_file`](Self::with_file) for the same path); callers that need other `ty.toml`
+    /// sections alongside the Python environment configuration should write them through
+    /// `with_file` using the same `[environment]` keys instead.
+    fn write_environment_options(self) -> Result<Self> {
+        let mut content = String::from("[environment]\n");
+        if let Some(version) = &self.python_version {
+            content.push_str(&format!("python-version = \"{version}\"\n"));
+        }
+        if let Some(venv) = &self.venv {
+            content.push_str(&format!("python = \"{venv}\"\n"));
+        }
+
+        self.with_file("ty.toml", content)
+    }
+
     /// Write a file to the test directory
     pub(crate) fn with_file(
         self,
@@ -1280,7 +1686,9 @@ impl TestServerBuilder {
             self.test_context,
             self.client_capabilities,
             self.initialization_options,
+            self.extra_initialization_options,
             self.env_vars,
+            self.middleware,
         )
     }
 }
@@ -1355,3 +1763,37 @@ impl TestContext {
 fn tempdir_filter(path: impl AsRef<str>) -> String {
     format!(r"{}\\?/?", regex::escape(path.as_ref()))
 }
+
+/// Snapshot filter for the opaque `data` handle that `textDocument/completion` responses carry
+/// for a later `completionItem/resolve` (the server-internal `CompletionResolveData`). Every test
+/// file with completion snapshots was re-deriving this same regex; shared here instead.
+pub(crate) const COMPLETION_RESOLVE_DATA_FILTER: (&str, &str) =
+    (r#"(?s)"data": \{.*?\}"#, r#""data": "[DATA]""#);
+
+/// Finds a `<CURSOR>` marker in `content`, returning the content with the marker removed and the
+/// [`Position`] it marked.
+///
+/// Several tests already embed `<CURSOR>` in their fixture source as a human-readable pointer to
+/// the position under test, but still separately hand-count the equivalent `Position::new(line,
+/// character)` to actually send in the request - the marker itself was purely decorative. This
+/// makes it load-bearing instead, so there's a single source of truth for "where".
+pub(crate) fn cursor_position(content: &str) -> (String, Position) {
+    const MARKER: &str = "<CURSOR>";
+
+    let offset = content
+        .find(MARKER)
+        .expect("content passed to `cursor_position` must contain a `<CURSOR>` marker");
+    let before = &content[..offset];
+    let line = before.matches('\n').count() as u32;
+    let character = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or(before)
+        .encode_utf16()
+        .count() as u32;
+
+    let mut without_marker = content.to_string();
+    without_marker.replace_range(offset..offset + MARKER.len(), "");
+
+    (without_marker, Position::new(line, character))
+}
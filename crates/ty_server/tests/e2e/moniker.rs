@@ -0,0 +1,64 @@
+use crate::TestServerBuilder;
+use lsp_types::request::MonikerRequest;
+use lsp_types::{
+    MonikerKind, MonikerParams, PartialResultParams, Position, TextDocumentIdentifier,
+    TextDocumentPositionParams, WorkDoneProgressParams,
+};
+
+#[test]
+fn moniker_for_function_definition() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "def test(): ...\n", 1);
+
+    let request_id = server.send_request::<MonikerRequest>(MonikerParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: server.file_uri("foo.py"),
+            },
+            position: Position::new(0, 5),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let monikers = server
+        .await_response::<MonikerRequest>(&request_id)
+        .expect("should find a moniker for the function definition");
+
+    assert_eq!(monikers.len(), 1);
+    assert_eq!(monikers[0].identifier, "foo.test");
+    assert_eq!(monikers[0].kind, Some(MonikerKind::Export));
+
+    Ok(())
+}
+
+#[test]
+fn no_moniker_for_literal() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "x = 1\n", 1);
+
+    let request_id = server.send_request::<MonikerRequest>(MonikerParams {
+        text_document_position_params: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: server.file_uri("foo.py"),
+            },
+            position: Position::new(0, 4),
+        },
+        work_done_progress_params: WorkDoneProgressParams::default(),
+        partial_result_params: PartialResultParams::default(),
+    });
+
+    let monikers = server.await_response::<MonikerRequest>(&request_id);
+
+    assert!(monikers.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+use ruff_db::system::SystemPath;
+use ty_server::ClientOptions;
+
+use crate::TestServerBuilder;
+
+/// Tests that each workspace folder in a multi-root workspace keeps its own settings, and that
+/// LSP requests for a document are resolved against the settings of the folder that owns it,
+/// rather than some other folder's (or the first folder's) settings.
+#[test]
+fn requests_use_the_owning_folders_settings() -> Result<()> {
+    let disabled_root = SystemPath::new("disabled");
+    let enabled_root = SystemPath::new("enabled");
+
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(
+            disabled_root,
+            Some(ClientOptions::default().with_disable_language_services(true)),
+        )?
+        .with_workspace(enabled_root, Some(ClientOptions::default()))?
+        .with_file(disabled_root.join("foo.py"), "def test(): ...\n\ntest()\n")?
+        .with_file(enabled_root.join("foo.py"), "def test(): ...\n\ntest()\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let disabled_uri = server.file_uri(disabled_root.join("foo.py"));
+    let enabled_uri = server.file_uri(enabled_root.join("foo.py"));
+
+    server.open_text_document(disabled_root.join("foo.py"), "def test(): ...\n\ntest()\n", 1);
+    server.open_text_document(enabled_root.join("foo.py"), "def test(): ...\n\ntest()\n", 1);
+
+    let position = lsp_types::Position {
+        line: 2,
+        character: 0,
+    };
+
+    assert_eq!(
+        server.goto_definition(&disabled_uri, position),
+        None,
+        "Language services are disabled for the `disabled` workspace, so goto-definition \
+        shouldn't return a result"
+    );
+
+    assert!(
+        server.goto_definition(&enabled_uri, position).is_some(),
+        "Language services are enabled for the `enabled` workspace, so goto-definition should \
+        resolve the definition of `test`"
+    );
+
+    Ok(())
+}
@@ -5,7 +5,10 @@ use ty_server::ClientOptions;
 
 use crate::{TestServer, TestServerBuilder};
 
-static FILTERS: &[(&str, &str)] = &[(r#""sortText": "[0-9 ]+""#, r#""sortText": "[RANKING]""#)];
+static FILTERS: &[(&str, &str)] = &[
+    (r#""sortText": "[0-9 ]+""#, r#""sortText": "[RANKING]""#),
+    crate::COMPLETION_RESOLVE_DATA_FILTER,
+];
 
 #[test]
 fn publish_diagnostics_open() -> anyhow::Result<()> {
@@ -432,6 +435,92 @@ b: Litera
     Ok(())
 }
 
+/// Tests that hovering over a name in one cell that's bound in an earlier cell resolves the
+/// type inferred for that binding, confirming that hover uses each cell's own text offsets
+/// together with the cross-cell binding information built up from the earlier cells.
+#[test]
+fn hover_cross_cell_binding() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.initialization_result().unwrap();
+
+    let mut builder = NotebookBuilder::virtual_file("src/test.ipynb");
+
+    builder.add_python_cell(
+        r#"def greet(name: str) -> str:
+    return f"Hello, {name}!"
+"#,
+    );
+
+    let second_cell = builder.add_python_cell(
+        r#"message = greet("ty")
+"#,
+    );
+
+    builder.open(&mut server);
+
+    server.collect_publish_diagnostic_notifications(2);
+
+    let hover = server
+        .hover(&second_cell, Position::new(0, 10))
+        .expect("Can hover over `greet`");
+
+    let lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("Expected markup hover contents, got: {:?}", hover.contents);
+    };
+
+    assert!(
+        markup.value.contains("def greet(name: str) -> str"),
+        "Expected hover to show the signature of `greet` defined in the first cell, got: {}",
+        markup.value
+    );
+
+    Ok(())
+}
+
+/// Tests that a binding from the first cell of a notebook keeps its real inferred type all the
+/// way in a much later cell, not just in the cell immediately after it, confirming that
+/// cross-cell binding information isn't limited to adjacent cells.
+#[test]
+fn hover_cross_cell_binding_many_cells_later() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.initialization_result().unwrap();
+
+    let mut builder = NotebookBuilder::virtual_file("src/test.ipynb");
+
+    builder.add_python_cell(r#"df = {"a": 1, "b": 2}"#);
+    builder.add_python_cell(r#"print("setting things up")"#);
+    builder.add_python_cell(r#"print("still setting things up")"#);
+    builder.add_python_cell(r#"print("almost there")"#);
+    let fifth_cell = builder.add_python_cell(r#"df["a"]"#);
+
+    builder.open(&mut server);
+
+    server.collect_publish_diagnostic_notifications(5);
+
+    let hover = server
+        .hover(&fifth_cell, Position::new(0, 0))
+        .expect("Can hover over `df`");
+
+    let lsp_types::HoverContents::Markup(markup) = hover.contents else {
+        panic!("Expected markup hover contents, got: {:?}", hover.contents);
+    };
+
+    assert!(
+        markup.value.contains("dict[Unknown | str, Unknown | int]"),
+        "Expected hover in the fifth cell to show the dict type inferred for `df` in the \
+        first cell, got: {}",
+        markup.value
+    );
+
+    Ok(())
+}
+
 fn semantic_tokens_full_for_cell(
     server: &mut TestServer,
     cell_uri: &lsp_types::Url,
@@ -0,0 +1,90 @@
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn local_binding_returns_identifier_range() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def test(): ...
+
+test()
+"#,
+        1,
+    );
+
+    let response = server
+        .prepare_rename(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 0,
+                character: 5,
+            },
+        )
+        .expect("`test` is a renameable symbol");
+
+    assert_json_snapshot!(response);
+
+    Ok(())
+}
+
+#[test]
+fn import_module_component_is_not_renameable() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"import os
+
+os.getcwd()
+"#,
+        1,
+    );
+
+    let response = server.prepare_rename(
+        &server.file_uri("foo.py"),
+        lsp_types::Position {
+            line: 0,
+            character: 8,
+        },
+    );
+
+    assert_eq!(response, None);
+
+    Ok(())
+}
+
+#[test]
+fn keyword_is_not_renameable() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"if True:
+    pass
+"#,
+        1,
+    );
+
+    let response = server.prepare_rename(
+        &server.file_uri("foo.py"),
+        lsp_types::Position {
+            line: 0,
+            character: 0,
+        },
+    );
+
+    assert_eq!(response, None);
+
+    Ok(())
+}
@@ -91,6 +91,43 @@ def foo() -> str:
     Ok(())
 }
 
+#[test]
+fn document_diagnostic_caching_unknown_previous_result_id() -> Result<()> {
+    let _filter = filter_result_id();
+
+    let workspace_root = SystemPath::new("src");
+    let foo = SystemPath::new("src/foo.py");
+    let foo_content = "\
+def foo() -> str:
+    return 42
+";
+
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, None)?
+        .with_file(foo, foo_content)?
+        .enable_pull_diagnostics(true)
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(foo, foo_content, 1);
+
+    // A request with a previous result ID the server never issued should fall back to a full
+    // report instead of (incorrectly) treating it as unchanged.
+    let response =
+        server.document_diagnostic_request(foo, Some("not-a-real-result-id".to_string()));
+
+    match response {
+        lsp_types::DocumentDiagnosticReportResult::Report(
+            lsp_types::DocumentDiagnosticReport::Full(report),
+        ) => {
+            assert_eq!(report.full_document_diagnostic_report.items.len(), 1);
+        }
+        _ => panic!("Expected a full report when the previous result ID is unrecognized"),
+    }
+
+    Ok(())
+}
+
 #[test]
 fn document_diagnostic_caching_changed() -> Result<()> {
     let _filter = filter_result_id();
@@ -159,6 +196,41 @@ def foo() -> str:
     Ok(())
 }
 
+#[test]
+fn workspace_diagnostic_no_errors() -> Result<()> {
+    let _filter = filter_result_id();
+
+    let workspace_root = SystemPath::new("src");
+    let foo = SystemPath::new("src/foo.py");
+    let foo_content = "\
+def foo() -> int:
+    return 42
+";
+
+    let mut server = create_workspace_server_with_file(workspace_root, foo, foo_content)?;
+
+    let response = send_workspace_diagnostic_request(&mut server);
+    let response = server.await_response::<WorkspaceDiagnosticRequest>(&response);
+
+    let items = match response {
+        WorkspaceDiagnosticReportResult::Report(report) => report.items,
+        WorkspaceDiagnosticReportResult::Partial(partial) => partial.items,
+    };
+
+    for item in &items {
+        match item {
+            WorkspaceDocumentDiagnosticReport::Full(report) => {
+                assert_eq!(report.full_document_diagnostic_report.items.len(), 0);
+            }
+            WorkspaceDocumentDiagnosticReport::Unchanged(_) => {
+                panic!("Expected a full report for the initial request")
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn workspace_diagnostic_caching() -> Result<()> {
     let _filter = filter_result_id();
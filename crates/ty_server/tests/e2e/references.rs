@@ -0,0 +1,100 @@
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn excludes_declaration_by_default() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def test(): ...
+
+test()
+test()
+"#,
+        1,
+    );
+
+    let references = server
+        .references(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 0,
+                character: 4,
+            },
+            false,
+        )
+        .expect("Can find references to `test`");
+
+    assert_json_snapshot!(references);
+
+    Ok(())
+}
+
+#[test]
+fn includes_declaration_when_requested() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def test(): ...
+
+test()
+"#,
+        1,
+    );
+
+    let references = server
+        .references(
+            &server.file_uri("foo.py"),
+            lsp_types::Position {
+                line: 0,
+                character: 4,
+            },
+            true,
+        )
+        .expect("Can find references to `test` including its declaration");
+
+    assert_json_snapshot!(references);
+
+    Ok(())
+}
+
+#[test]
+fn cross_file() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("lib.py", "def helper(): ...\n")?
+        .with_file("main.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "main.py",
+        r#"from lib import helper
+
+helper()
+"#,
+        1,
+    );
+
+    let references = server
+        .references(
+            &server.file_uri("main.py"),
+            lsp_types::Position {
+                line: 2,
+                character: 0,
+            },
+            false,
+        )
+        .expect("Can find references to `helper` across files");
+
+    assert_json_snapshot!(references);
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
+use crate::AwaitResponseError;
 use crate::TestServerBuilder;
 use crate::notebook::NotebookBuilder;
 use insta::assert_json_snapshot;
+use lsp_types::request::Rename;
+use lsp_types::{RenameParams, TextDocumentIdentifier, TextDocumentPositionParams};
 
 #[test]
 fn text_document() -> anyhow::Result<()> {
@@ -82,3 +85,43 @@ type Style = Literal["italic", "bold", "underline"]"#,
     server.collect_publish_diagnostic_notifications(2);
     Ok(())
 }
+
+#[test]
+fn rejects_invalid_identifier() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def test(): ...
+
+test()
+"#,
+        1,
+    );
+
+    let document = server.file_uri("foo.py");
+    let id = server.send_request::<Rename>(RenameParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier {
+                uri: document.clone(),
+            },
+            position: lsp_types::Position {
+                line: 0,
+                character: 5,
+            },
+        },
+        new_name: "class".to_string(),
+        work_done_progress_params: lsp_types::WorkDoneProgressParams::default(),
+    });
+
+    let error = server
+        .try_await_response::<Rename>(&id, None)
+        .expect_err("Renaming to a keyword should be rejected");
+
+    assert!(matches!(error, AwaitResponseError::RequestFailed(_)));
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use ty_server::tsp::TspContentEncoding;
+
+use crate::TestServerBuilder;
+
+#[test]
+fn returns_json_by_default() -> Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "def search_target(): ...\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let result = server.search_symbols_request("search_target", None);
+
+    assert!(
+        result
+            .symbols
+            .iter()
+            .any(|symbol| symbol.name == "search_target")
+    );
+    assert!(result.symbols_binary.is_none());
+    assert!(!result.incomplete);
+
+    Ok(())
+}
+
+#[test]
+fn uses_message_pack_when_negotiated() -> Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_raw_initialization_option("tspPayloadEncoding", "messagePack")
+        .with_file("foo.py", "def search_target(): ...\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let result = server.search_symbols_request("search_target", None);
+
+    assert!(result.symbols.is_empty());
+    let binary = result
+        .symbols_binary
+        .expect("MessagePack payload should be present once negotiated");
+    assert_eq!(binary.content_encoding, TspContentEncoding::MessagePack);
+
+    let symbols: Vec<lsp_types::SymbolInformation> = decode_symbols_binary(&binary);
+    assert!(symbols.iter().any(|symbol| symbol.name == "search_target"));
+
+    Ok(())
+}
+
+#[test]
+fn gzips_message_pack_payloads_past_the_size_threshold() -> Result<()> {
+    const SYMBOL_COUNT: usize = 300;
+
+    let mut content = String::new();
+    for i in 0..SYMBOL_COUNT {
+        content.push_str(&format!("def search_target_{i}(): ...\n"));
+    }
+
+    let mut server = TestServerBuilder::new()?
+        .with_raw_initialization_option("tspPayloadEncoding", "messagePack")
+        .with_file("foo.py", &content)?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let result = server.search_symbols_request("search_target", None);
+
+    assert!(result.symbols.is_empty());
+    let binary = result
+        .symbols_binary
+        .expect("MessagePack payload should be present once negotiated");
+    assert_eq!(binary.content_encoding, TspContentEncoding::MessagePackGzip);
+
+    let symbols: Vec<lsp_types::SymbolInformation> = decode_symbols_binary(&binary);
+    assert_eq!(symbols.len(), SYMBOL_COUNT);
+
+    Ok(())
+}
+
+#[test]
+fn container_name_is_omitted_unless_negotiated() -> Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "def search_target(): ...\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let result = server.search_symbols_request("search_target", None);
+
+    let symbol = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "search_target")
+        .expect("the defined function should be found");
+    assert_eq!(symbol.container_name, None);
+
+    Ok(())
+}
+
+#[test]
+fn container_name_is_included_once_negotiated() -> Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_raw_initialization_option("tspKnownResultFields", vec!["containerName"])
+        .with_file("foo.py", "def search_target(): ...\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let result = server.search_symbols_request("search_target", None);
+
+    let symbol = result
+        .symbols
+        .iter()
+        .find(|symbol| symbol.name == "search_target")
+        .expect("the defined function should be found");
+    assert_eq!(symbol.container_name.as_deref(), Some("foo"));
+
+    Ok(())
+}
+
+/// Decodes a [`ty_server::tsp::TspBinaryPayload`], gunzipping it first if necessary.
+fn decode_symbols_binary<T: serde::de::DeserializeOwned>(
+    binary: &ty_server::tsp::TspBinaryPayload,
+) -> T {
+    use base64::Engine as _;
+
+    let compressed_or_plain = base64::engine::general_purpose::STANDARD
+        .decode(&binary.data)
+        .expect("payload should be valid base64");
+
+    let bytes = match binary.content_encoding {
+        TspContentEncoding::MessagePack => compressed_or_plain,
+        TspContentEncoding::MessagePackGzip => {
+            use std::io::Read as _;
+
+            let mut decoder = flate2::read::GzDecoder::new(compressed_or_plain.as_slice());
+            let mut decompressed = Vec::new();
+            decoder
+                .read_to_end(&mut decompressed)
+                .expect("payload should be valid gzip");
+            decompressed
+        }
+    };
+
+    rmp_serde::from_slice(&bytes).expect("payload should be valid MessagePack")
+}
@@ -0,0 +1,54 @@
+use crate::TestServerBuilder;
+use insta::assert_json_snapshot;
+
+#[test]
+fn expand_selection_from_nested_expression() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(
+        "foo.py",
+        r#"def f():
+    x = 1 + 2
+"#,
+        1,
+    );
+
+    let ranges = server
+        .selection_range(
+            &server.file_uri("foo.py"),
+            vec![lsp_types::Position {
+                line: 1,
+                character: 9,
+            }],
+        )
+        .expect("Can compute a selection range chain");
+
+    assert_json_snapshot!(ranges);
+
+    Ok(())
+}
+
+#[test]
+fn no_selection_range_outside_document() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "x = 1\n", 1);
+
+    let ranges = server.selection_range(
+        &server.file_uri("foo.py"),
+        vec![lsp_types::Position {
+            line: 5,
+            character: 0,
+        }],
+    );
+
+    assert!(ranges.is_none() || ranges.unwrap().is_empty());
+
+    Ok(())
+}
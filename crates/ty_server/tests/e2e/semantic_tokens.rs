@@ -1,4 +1,5 @@
 use anyhow::Result;
+use lsp_types::SemanticTokensResult;
 use ruff_db::system::SystemPath;
 
 use crate::TestServerBuilder;
@@ -37,6 +38,73 @@ fn multiline_token_client_not_supporting_multiline_tokens() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn delta_request_with_stale_result_id_falls_back_to_full_tokens() -> Result<()> {
+    let workspace_root = SystemPath::new("src");
+    let foo = SystemPath::new("src/foo.py");
+
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, None)?
+        .with_file(foo, "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(foo, "x = 1\n", 1);
+
+    let delta = server
+        .semantic_tokens_full_delta_request(&server.file_uri(foo), "not-a-real-result-id")
+        .expect("Server responds even when it doesn't recognize the previous result id");
+
+    assert!(matches!(delta, lsp_types::SemanticTokensFullDeltaResult::Tokens(_)));
+
+    Ok(())
+}
+
+#[test]
+fn delta_request_after_appending_a_line_returns_an_edit() -> Result<()> {
+    let workspace_root = SystemPath::new("src");
+    let foo = SystemPath::new("src/foo.py");
+
+    let mut server = TestServerBuilder::new()?
+        .with_workspace(workspace_root, None)?
+        .with_file(foo, "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(foo, "x = 1\n", 1);
+
+    let uri = server.file_uri(foo);
+    let SemanticTokensResult::Tokens(first) = server
+        .semantic_tokens_full_request(&uri)
+        .expect("Can compute semantic tokens")
+    else {
+        panic!("Expected a full token set, not a partial result");
+    };
+    let previous_result_id = first.result_id.expect("Server assigns a result id");
+
+    server.change_text_document(
+        foo,
+        vec![lsp_types::TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "x = 1\ny = 2\n".to_string(),
+        }],
+        2,
+    );
+
+    let delta = server
+        .semantic_tokens_full_delta_request(&uri, &previous_result_id)
+        .expect("Can compute a semantic tokens delta");
+
+    let lsp_types::SemanticTokensFullDeltaResult::TokensDelta(delta) = delta else {
+        panic!("Expected a delta response since the previous result id is still valid");
+    };
+
+    insta::assert_json_snapshot!(delta.edits);
+
+    Ok(())
+}
+
 #[test]
 fn multiline_token_client_supporting_multiline_tokens() -> Result<()> {
     let workspace_root = SystemPath::new("src");
@@ -0,0 +1,78 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use lsp_types::Position;
+use ty_server::middleware::TspMiddleware;
+
+use crate::TestServerBuilder;
+
+/// A [`TspMiddleware`] that records the method name of every hook invocation it observes, so a
+/// test can assert on them after the middleware has been handed off to the server.
+#[derive(Default)]
+struct RecordingMiddleware {
+    before_dispatch: Arc<Mutex<Vec<String>>>,
+    after_respond: Arc<Mutex<Vec<String>>>,
+}
+
+impl TspMiddleware for RecordingMiddleware {
+    fn before_dispatch(&self, method: &str, _params: &serde_json::Value) {
+        self.before_dispatch.lock().unwrap().push(method.to_string());
+    }
+
+    fn after_respond(&self, method: &str, _duration: Duration) {
+        self.after_respond.lock().unwrap().push(method.to_string());
+    }
+}
+
+#[test]
+fn middleware_observes_a_dispatched_request() -> Result<()> {
+    let middleware = RecordingMiddleware::default();
+    let before_dispatch = Arc::clone(&middleware.before_dispatch);
+    let after_respond = Arc::clone(&middleware.after_respond);
+
+    let mut server = TestServerBuilder::new()?
+        .with_middleware(Box::new(middleware))
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document("foo.py", "def test(): ...\n", 1);
+    server.hover_request("foo.py", Position::new(0, 5));
+
+    assert!(
+        before_dispatch
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|method| method == "textDocument/hover")
+    );
+    assert!(
+        after_respond
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|method| method == "textDocument/hover")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn set_background_worker_threads_does_not_disrupt_the_server() -> Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("foo.py", "")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server
+        .handle()
+        .set_background_worker_threads(NonZeroUsize::new(2).unwrap());
+
+    server.open_text_document("foo.py", "def test(): ...\n", 1);
+    let hover = server.hover_request("foo.py", Position::new(0, 5));
+    assert!(hover.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use lsp_types::notification::PublishDiagnostics;
+use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+use ruff_db::system::SystemPath;
+
+use crate::TestServerBuilder;
+
+/// The visible document's diagnostics publish before the background document's, even though the
+/// background document was edited first.
+///
+/// Both documents get the same generous debounce window and are changed back to back with no
+/// delay in between, so their debounce deadlines land together; the order the two
+/// `publishDiagnostics` notifications arrive in is then governed entirely by the server's
+/// visibility-based sort of due, debounced publishes, not by edit order.
+#[test]
+fn visible_document_publishes_before_background_document() -> Result<()> {
+    let workspace_root = SystemPath::new("src");
+    let background = SystemPath::new("src/background.py");
+    let visible = SystemPath::new("src/visible.py");
+    let content = "x = 1\n";
+
+    let mut server = TestServerBuilder::new()?
+        .with_raw_initialization_option("diagnosticsDebounceMs", 300)
+        .with_workspace(workspace_root, None)?
+        .with_file(background, content)?
+        .with_file(visible, content)?
+        .enable_pull_diagnostics(false)
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    server.open_text_document(background, content, 1);
+    let _ = server.await_notification::<PublishDiagnostics>();
+    server.open_text_document(visible, content, 1);
+    let _ = server.await_notification::<PublishDiagnostics>();
+
+    let visible_uri = server.file_uri(visible);
+    server.notify_visible_ranges(
+        &visible_uri,
+        vec![Range::new(Position::new(0, 0), Position::new(1, 0))],
+    );
+
+    let change = |text: &str| {
+        vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: text.to_string(),
+        }]
+    };
+
+    server.change_text_document(background, change("x = 2\n"), 2);
+    server.change_text_document(visible, change("x = 2\n"), 2);
+
+    let first = server.await_notification::<PublishDiagnostics>();
+    let second = server.await_notification::<PublishDiagnostics>();
+
+    assert_eq!(
+        first.uri, visible_uri,
+        "the visible document should publish first"
+    );
+    assert_eq!(second.uri, server.file_uri(background));
+
+    Ok(())
+}
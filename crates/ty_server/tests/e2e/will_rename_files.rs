@@ -0,0 +1,55 @@
+use crate::TestServerBuilder;
+use lsp_types::request::WillRenameFiles;
+use lsp_types::{FileRename, RenameFilesParams};
+
+#[test]
+fn updates_imports_of_renamed_module() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("old_mod.py", "def f(): ...\n")?
+        .with_file("main.py", "import old_mod\n\nold_mod.f()\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let request_id = server.send_request::<WillRenameFiles>(RenameFilesParams {
+        files: vec![FileRename {
+            old_uri: server.file_uri("old_mod.py").to_string(),
+            new_uri: server.file_uri("new_mod.py").to_string(),
+        }],
+    });
+
+    let edit = server
+        .await_response::<WillRenameFiles>(&request_id)
+        .expect("should compute edits for the renamed module's imports");
+
+    let changes = edit.changes.expect("edit should contain file changes");
+    let main_edits = changes
+        .get(&server.file_uri("main.py"))
+        .expect("main.py should have an edit updating its import");
+
+    assert_eq!(main_edits.len(), 1);
+    assert_eq!(main_edits[0].new_text, "new_mod");
+
+    Ok(())
+}
+
+#[test]
+fn no_edits_for_unrelated_rename() -> anyhow::Result<()> {
+    let mut server = TestServerBuilder::new()?
+        .with_file("old_mod.py", "def f(): ...\n")?
+        .with_file("main.py", "x = 1\n")?
+        .build()
+        .wait_until_workspaces_are_initialized();
+
+    let request_id = server.send_request::<WillRenameFiles>(RenameFilesParams {
+        files: vec![FileRename {
+            old_uri: server.file_uri("old_mod.py").to_string(),
+            new_uri: server.file_uri("new_mod.py").to_string(),
+        }],
+    });
+
+    let edit = server.await_response::<WillRenameFiles>(&request_id);
+
+    assert!(edit.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,25 @@
+//! Fuzzer harness that checks the `typeServer/*` (TSP) message deserialization paths don't
+//! panic on arbitrary JSON.
+//!
+//! `ty_server` deserializes these straight from untrusted input: `SearchSymbolsParams` and
+//! `VisibleRangesParams` arrive as request/notification params from the client.
+
+#![no_main]
+
+use libfuzzer_sys::{Corpus, fuzz_target};
+use ty_server::{SearchSymbolsParams, VisibleRangesParams};
+
+fn do_fuzz(case: &[u8]) -> Corpus {
+    let Ok(text) = std::str::from_utf8(case) else {
+        return Corpus::Reject;
+    };
+
+    // Neither of these is expected to succeed on arbitrary input; the only thing under test is
+    // that deserialization reports an error instead of panicking.
+    let _ = serde_json::from_str::<SearchSymbolsParams>(text);
+    let _ = serde_json::from_str::<VisibleRangesParams>(text);
+
+    Corpus::Keep
+}
+
+fuzz_target!(|case: &[u8]| -> Corpus { do_fuzz(case) });